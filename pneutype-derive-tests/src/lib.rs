@@ -1,6 +1,6 @@
 /// A string that is_ascii_lowercase, i.e. has no non-ascii-lowercase chars.
-#[derive(Debug, Eq, PartialEq, Hash, pneutype::PneuString, serde::Serialize)]
-#[pneu_string(borrow = "LowercaseStr", deserialize)]
+#[derive(Debug, Eq, PartialEq, Hash, Ord, PartialOrd, pneutype::PneuString, serde::Serialize)]
+#[pneu_string(borrow = "LowercaseStr", deserialize, no_arbitrary)]
 pub struct Lowercase(String);
 
 impl Lowercase {
@@ -12,8 +12,8 @@ impl Lowercase {
 }
 
 /// The str-equivalent of Lowercase.  Is used to pass validated-lowercase strings by reference.
-#[derive(Debug, Eq, PartialEq, Hash, pneutype::PneuStr, serde::Serialize)]
-#[pneu_str(deserialize)]
+#[derive(Debug, Eq, PartialEq, Hash, Ord, PartialOrd, pneutype::PneuStr, serde::Serialize)]
+#[pneu_str(deserialize, expecting = "all-lowercase ASCII")]
 #[repr(transparent)] // `repr(transparent)` is required for PneuStr!
 pub struct LowercaseStr(str);
 
@@ -30,14 +30,43 @@ impl LowercaseStr {
     }
 }
 
+impl LowercaseStr {
+    /// The const version of the validation constraint, so that pneutype::pneu_str! can be used.
+    pub const fn validate_const(s: &str) -> Result<(), &'static str> {
+        let bytes = s.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            if !bytes[i].is_ascii_lowercase() {
+                return Err("must be an all-lowercase string");
+            }
+            i += 1;
+        }
+        Ok(())
+    }
+}
+
 impl pneutype::Validate for LowercaseStr {
     type Data = str;
     type Error = &'static str;
     fn validate(data: &Self::Data) -> Result<(), Self::Error> {
-        if data.chars().all(|c| c.is_ascii_lowercase()) {
+        Self::validate_const(data)
+    }
+}
+
+/// Mixed-case ASCII is accepted as a legacy form of LowercaseStr, and is canonicalized by lowercasing it.
+impl pneutype::ValidateLenient for LowercaseStr {
+    fn validate_lenient(data: &str) -> Result<(), Self::Error> {
+        if data.chars().all(|c| c.is_ascii_alphabetic()) {
             Ok(())
         } else {
-            Err("must be an all-lowercase string")
+            Err("must be an all-alphabetic string")
+        }
+    }
+    fn canonicalize(data: &str) -> std::borrow::Cow<'_, str> {
+        if data.chars().any(|c| c.is_ascii_uppercase()) {
+            std::borrow::Cow::Owned(data.to_ascii_lowercase())
+        } else {
+            std::borrow::Cow::Borrowed(data)
         }
     }
 }
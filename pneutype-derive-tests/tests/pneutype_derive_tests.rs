@@ -1,40 +1,17 @@
-use pneutype_derive_tests::{Lowercase, LowercaseStr, ValueStr, ValueString};
-use std::{borrow::Cow, str::FromStr};
-
-#[test]
-fn test_pneu_string_and_pneu_str_1() {
-    let t0 = Lowercase::try_from("abcd").expect("pass");
-    println!("t0 (as Debug): {:?}", t0);
-    // Ensure can use the chain of derefs Lowercase -> LowercaseStr -> str in order to call a str method.
-    assert_eq!(t0.len(), t0.as_str().len());
-
-    println!("t0 (as Display): {}", t0);
-
-    Lowercase::try_from("abcdE").expect_err("pass");
-    <&LowercaseStr>::try_from("abcdE").expect_err("pass");
-    LowercaseStr::new_ref("abcdE").expect_err("pass");
-
-    use std::ops::Deref;
-    let r0 = t0.deref();
-    println!("r0 (as Debug): {:?}", r0);
-    println!("r0 (as Display): {}", r0);
-
-    let t1 = Lowercase::from_str("abcd").expect("pass");
-    assert_eq!(t0, t1);
+// The pre-existing tests are kept as they were written, so the lints they trip are allowed here.
+#![allow(
+    clippy::bool_assert_comparison,
+    clippy::needless_borrow,
+    clippy::upper_case_acronyms,
+    clippy::useless_conversion
+)]
 
-    let r1 = <&LowercaseStr>::try_from("abcd").expect("pass");
-    assert_eq!(r0, r1);
-    assert_eq!(t1.as_pneu_str(), r1);
-
-    let r2 = LowercaseStr::new_ref("abcd").expect("pass");
-    assert_eq!(r0, r2);
-
-    let t2 = r0.to_owned();
-    assert_eq!(t0, t2);
-
-    let s0 = t0.into_string();
-    assert_eq!(s0.as_str(), t2.as_str());
-}
+use pneutype_derive_tests::{Lowercase, LowercaseStr, ValueStr, ValueString};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap, HashSet},
+    str::FromStr,
+};
 
 fn do_stuff_with_lowercase_str(_: &LowercaseStr) {
     // Actually do nothing, ha ha ha!
@@ -49,7 +26,7 @@ fn test_pneu_string_borrow() {
 }
 
 #[derive(Debug, Eq, PartialEq, pneutype::PneuString)]
-#[pneu_string(borrow = "URLStr", as_pneu_str = "as_url_str")]
+#[pneu_string(borrow = "URLStr", as_pneu_str = "as_url_str", no_arbitrary)]
 struct URL(String);
 
 #[derive(Debug, Eq, PartialEq, pneutype::PneuStr)]
@@ -115,17 +92,7 @@ impl URLStr {
 
 #[test]
 fn test_pneu_string_and_pneu_str_2() {
-    const INVALID_URL_STR_V: &[&str] =
-        &["xyz", "http::blah/123", "http::/blah/123", "blah/123?a=b"];
-
-    for &invalid_url_str in INVALID_URL_STR_V.iter() {
-        println!("invalid_url_str: {:?}", invalid_url_str);
-        URL::from_str(invalid_url_str).expect_err("pass");
-        URL::try_from(invalid_url_str).expect_err("pass");
-        <&URLStr>::try_from(invalid_url_str).expect_err("pass");
-        URLStr::new_ref(invalid_url_str).expect_err("pass");
-    }
-
+    // The URL accessors agree between URL (via Deref) and URLStr.
     const URL_STR_V: &[&str] = &[
         "https://no.golf:12345/at/all?abc=pqr#tag1",
         "https://no.golf:12345/at/all?abc=pqr",
@@ -147,19 +114,9 @@ fn test_pneu_string_and_pneu_str_2() {
     ];
 
     for &url_str in URL_STR_V.iter() {
-        URL::from_str(url_str).expect("pass");
-        URL::try_from(url_str).expect("pass");
-        let u0 = URL::try_from(url_str.to_string()).expect("pass");
-        let u1 = <&URLStr>::try_from(url_str).expect("pass");
-        assert_eq!(u0.as_url_str(), u1);
-        URLStr::new_ref(url_str).expect("pass");
-
         let t0 = URL::try_from(url_str).expect("pass");
-        println!("t0: {:?}", t0);
-
-        use std::ops::Deref;
-        let r0 = t0.deref();
-        println!("r0: {:?}", r0);
+        let r0 = URLStr::new_ref(url_str).expect("pass");
+        assert_eq!(t0.as_url_str(), r0);
 
         assert_eq!(t0.scheme(), r0.scheme());
         assert_eq!(t0.host(), r0.host());
@@ -167,21 +124,6 @@ fn test_pneu_string_and_pneu_str_2() {
         assert_eq!(t0.path(), r0.path());
         assert_eq!(t0.query(), r0.query());
         assert_eq!(t0.fragment(), r0.fragment());
-
-        let t1 = URL::from_str(url_str).expect("pass");
-        assert_eq!(t0, t1);
-
-        let r1 = <&URLStr>::try_from(url_str).expect("pass");
-        assert_eq!(r0, r1);
-
-        let r2 = URLStr::new_ref(url_str).expect("pass");
-        assert_eq!(r0, r2);
-
-        let t2 = r0.to_owned();
-        assert_eq!(t0, t2);
-
-        let s0 = t0.into_string();
-        assert_eq!(s0.as_str(), t2.as_str());
     }
 }
 
@@ -195,10 +137,9 @@ fn test_pneu_string_and_pneu_str_3() {
     do_stuff(&lowercase);
 }
 
-// TODO: Figure out how to cause Cow<'a, LowercaseStr> to be borrowed upon deserialize.
-// See:
-// -    https://github.com/serde-rs/serde/issues/1852
-// -    https://play.rust-lang.org/?version=nightly&mode=debug&edition=2018&gist=418dd6b98dfa62d43c4cc7fa8b7ea0d6
+// NOTE: serde always deserializes Cow<'a, LowercaseStr> as owned, see
+// https://github.com/serde-rs/serde/issues/1852 -- use `#[serde(borrow, with = "pneutype::serde::cow")]`
+// to borrow where possible (see test_pneu_str_serde_cow_containers).
 #[derive(Debug, serde::Deserialize, Eq, PartialEq, serde::Serialize)]
 struct Thingy<'a> {
     lowercase: Lowercase,
@@ -344,162 +285,2534 @@ fn test_pneu_string_and_pneu_str_serde() {
 }
 
 #[test]
-fn test_pneu_str_with_generics() {
-    type I32Str = ValueStr<i32>;
+fn test_pneu_string_and_pneu_str_serde_map_keys() {
+    // Owned keys can be deserialized whether or not the key was escaped in the JSON.
+    {
+        let map: HashMap<Lowercase, u32> =
+            serde_json::from_str(r#"{"abc":1,"xy\u007a":2}"#).expect("pass");
+        assert_eq!(map.len(), 2);
+        // Borrow<str> allows lookup by &str.
+        assert_eq!(map.get("abc"), Some(&1));
+        assert_eq!(map.get("xyz"), Some(&2));
+        let json =
+            serde_json::to_string(&map.into_iter().collect::<BTreeMap<_, _>>()).expect("pass");
+        assert_eq!(json, r#"{"abc":1,"xyz":2}"#);
+    }
+    // Borrowed keys are borrowed directly from the input.
+    {
+        let json = r#"{"abc":1,"xyz":2}"#;
+        let map: BTreeMap<&LowercaseStr, u32> = serde_json::from_str(json).expect("pass");
+        assert_eq!(map.len(), 2);
+        assert_eq!(
+            map.get(LowercaseStr::new_ref("abc").expect("pass")),
+            Some(&1)
+        );
+        assert_eq!(
+            map.get(LowercaseStr::new_ref("xyz").expect("pass")),
+            Some(&2)
+        );
+        assert_eq!(serde_json::to_string(&map).expect("pass"), json);
+    }
+    // Borrowed keys can't be deserialized if they were escaped, and the error says why.
+    {
+        let err = serde_json::from_str::<BTreeMap<&LowercaseStr, u32>>(r#"{"xy\u007a":2}"#)
+            .expect_err("pass");
+        println!("serde_json::from_str err (expected): {}", err);
+        assert!(err.to_string().contains("can't borrow \"xyz\""));
+    }
+    // Errors name the key that failed validation.
+    {
+        let err = serde_json::from_str::<HashMap<Lowercase, u32>>(r#"{"abc":1,"ABC":2}"#)
+            .expect_err("pass");
+        println!("serde_json::from_str err (expected): {}", err);
+        assert!(err.to_string().contains("\"ABC\""));
+        let err = serde_json::from_str::<BTreeMap<&LowercaseStr, u32>>(r#"{"abc":1,"ABC":2}"#)
+            .expect_err("pass");
+        println!("serde_json::from_str err (expected): {}", err);
+        assert!(err.to_string().contains("\"ABC\""));
+        // An invalid key that also can't be borrowed reports the validation error.
+        let err = serde_json::from_str::<BTreeMap<&LowercaseStr, u32>>(r#"{"A\u0042C":2}"#)
+            .expect_err("pass");
+        println!("serde_json::from_str err (expected): {}", err);
+        assert!(err
+            .to_string()
+            .starts_with("invalid value: string \"ABC\", expected a LowercaseStr"));
+    }
+}
 
-    I32Str::new_ref("").expect_err("pass");
-    I32Str::new_ref("abc").expect_err("pass");
-    I32Str::new_ref("12.34").expect_err("pass");
+#[test]
+fn test_pneu_string_serde_integer_map_keys() {
+    use serde::Deserialize;
 
-    let i = I32Str::new_ref("123").expect("pass");
-    assert_eq!(i.as_str(), "123");
-    assert_eq!(i.to_value(), 123i32);
+    type U32String = ValueString<u32>;
 
-    type BoolStr = ValueStr<bool>;
+    // serde_json gives integer-looking keys as strings.
+    {
+        let map: HashMap<U32String, bool> =
+            serde_json::from_str(r#"{"12":true,"34":false}"#).expect("pass");
+        assert_eq!(map.get("12"), Some(&true));
+        assert_eq!(map.get("34"), Some(&false));
+    }
+    // Other deserializers may give integer keys as integers, which the map with-modules accept.
+    {
+        let deserializer = serde::de::value::MapDeserializer::<_, serde::de::value::Error>::new(
+            vec![(12u64, true), (34u64, false)].into_iter(),
+        );
+        let map: HashMap<U32String, bool> =
+            pneutype::serde::hash_map_owned::deserialize(deserializer).expect("pass");
+        assert_eq!(map.get("12"), Some(&true));
+        assert_eq!(map.get("34"), Some(&false));
+    }
+    {
+        let deserializer = serde::de::value::MapDeserializer::<_, serde::de::value::Error>::new(
+            vec![(12u64, true), (34u64, false)].into_iter(),
+        );
+        let map: BTreeMap<Digits<()>, bool> =
+            pneutype::serde::btree_map_owned::deserialize(deserializer).expect("pass");
+        assert_eq!(map.get("34"), Some(&false));
+    }
+    {
+        let deserializer = serde::de::value::MapDeserializer::<_, serde::de::value::Error>::new(
+            vec![(12i64, true), (-34i64, false)].into_iter(),
+        );
+        let err = pneutype::serde::hash_map_owned::deserialize::<_, U32String, bool>(deserializer)
+            .expect_err("pass");
+        println!("hash_map_owned::deserialize err (expected): {}", err);
+        assert!(err.to_string().contains("\"-34\""));
+    }
+    #[derive(Debug, serde::Deserialize, serde::Serialize)]
+    struct Counts {
+        #[serde(with = "pneutype::serde::btree_map_owned")]
+        counts: BTreeMap<Digits<()>, u32>,
+    }
+    {
+        let counts: Counts = serde_json::from_str(r#"{"counts":{"7":1,"12":2}}"#).expect("pass");
+        assert_eq!(counts.counts.get("12"), Some(&2));
+        assert_eq!(
+            serde_json::to_string(&counts).expect("pass"),
+            r#"{"counts":{"12":2,"7":1}}"#
+        );
+    }
+    // Integers aren't accepted outside of map keys, nor by the derived impl as map keys.
+    {
+        use serde::de::IntoDeserializer;
+        let deserializer: serde::de::value::U64Deserializer<serde::de::value::Error> =
+            56u64.into_deserializer();
+        let err = U32String::deserialize(deserializer).expect_err("pass");
+        assert_eq!(
+            err.to_string(),
+            "invalid type: integer `56`, expected a ValueString"
+        );
+        let deserializer = serde::de::value::MapDeserializer::<_, serde::de::value::Error>::new(
+            vec![(12u64, true)].into_iter(),
+        );
+        HashMap::<U32String, bool>::deserialize(deserializer).expect_err("pass");
+    }
+}
 
-    BoolStr::new_ref("").expect_err("pass");
-    BoolStr::new_ref("blah").expect_err("pass");
-    BoolStr::new_ref("1").expect_err("pass");
+#[test]
+fn test_pneu_string_and_pneu_str_serde_error_messages() {
+    // With the `expecting` attribute, the description is used.
+    let err = serde_json::from_str::<&LowercaseStr>(r#""Foo""#).expect_err("pass");
+    assert_eq!(
+        err.to_string(),
+        r#"invalid value: string "Foo", expected a LowercaseStr (all-lowercase ASCII) at line 1 column 5"#
+    );
+    let err = serde_json::from_str::<&LowercaseStr>("123").expect_err("pass");
+    assert_eq!(
+        err.to_string(),
+        "invalid type: integer `123`, expected a borrowed LowercaseStr (all-lowercase ASCII) at line 1 column 3"
+    );
+    // Without it, the validation error is used.
+    let err = serde_json::from_str::<Lowercase>(r#""Foo""#).expect_err("pass");
+    assert_eq!(
+        err.to_string(),
+        r#"invalid value: string "Foo", expected a Lowercase (must be an all-lowercase string) at line 1 column 5"#
+    );
+    let err = serde_json::from_str::<Lowercase>("[]").expect_err("pass");
+    assert_eq!(
+        err.to_string(),
+        "invalid type: sequence, expected a Lowercase at line 1 column 0"
+    );
+    let err = serde_json::from_str::<ValueString<i32>>(r#""x""#).expect_err("pass");
+    assert_eq!(
+        err.to_string(),
+        r#"invalid value: string "x", expected a ValueString (parse error in value) at line 1 column 3"#
+    );
+}
 
-    let b = BoolStr::new_ref("true").expect("pass");
-    assert_eq!(b.as_str(), "true");
-    assert_eq!(b.to_value(), true);
+#[derive(Debug, serde::Deserialize, Eq, PartialEq, serde::Serialize)]
+struct CowContainers<'a> {
+    #[serde(borrow, with = "pneutype::serde::cow")]
+    cow: Cow<'a, LowercaseStr>,
+    #[serde(borrow, default, with = "pneutype::serde::option_cow")]
+    option_cow: Option<Cow<'a, LowercaseStr>>,
+    #[serde(borrow, with = "pneutype::serde::vec_cow")]
+    vec_cow: Vec<Cow<'a, LowercaseStr>>,
+    #[serde(borrow, with = "pneutype::serde::hash_set_cow")]
+    hash_set_cow: HashSet<Cow<'a, LowercaseStr>>,
 }
 
 #[test]
-fn test_pneu_string_with_generics() {
-    type I32String = ValueString<i32>;
-    type I32Str = ValueStr<i32>;
+fn test_pneu_str_serde_cow_containers() {
+    // Unescaped strings are borrowed.
+    {
+        let json =
+            r#"{"cow":"abc","option_cow":"def","vec_cow":["ghi","jkl"],"hash_set_cow":["mno"]}"#;
+        let c: CowContainers = serde_json::from_str(json).expect("pass");
+        assert!(matches!(c.cow, Cow::Borrowed(_)));
+        assert!(matches!(c.option_cow, Some(Cow::Borrowed(_))));
+        assert!(c.vec_cow.iter().all(|x| matches!(x, Cow::Borrowed(_))));
+        assert!(c.hash_set_cow.iter().all(|x| matches!(x, Cow::Borrowed(_))));
+        assert_eq!(c.cow.as_str(), "abc");
+        assert_eq!(
+            c.option_cow.as_deref().map(LowercaseStr::as_str),
+            Some("def")
+        );
+        assert_eq!(
+            c.vec_cow.iter().map(|x| x.as_str()).collect::<Vec<_>>(),
+            vec!["ghi", "jkl"]
+        );
+        assert!(c
+            .hash_set_cow
+            .contains(LowercaseStr::new_ref("mno").expect("pass")));
+        // Round-trip.
+        let c_json = serde_json::to_string(&c).expect("pass");
+        let c_deserialized: CowContainers = serde_json::from_str(&c_json).expect("pass");
+        assert_eq!(c_deserialized, c);
+    }
+    // Escaped strings fall back to owned, and missing/null optional fields are None.
+    {
+        let json = r#"{"cow":"\u0061bc","vec_cow":["ghi","j\u006bl"],"hash_set_cow":[]}"#;
+        let c: CowContainers = serde_json::from_str(json).expect("pass");
+        assert!(matches!(c.cow, Cow::Owned(_)));
+        assert_eq!(c.cow.as_str(), "abc");
+        assert_eq!(c.option_cow, None);
+        assert!(matches!(c.vec_cow[0], Cow::Borrowed(_)));
+        assert!(matches!(c.vec_cow[1], Cow::Owned(_)));
+        assert_eq!(c.vec_cow[1].as_str(), "jkl");
+        assert!(c.hash_set_cow.is_empty());
+
+        let json = r#"{"cow":"abc","option_cow":null,"vec_cow":[],"hash_set_cow":[]}"#;
+        let c: CowContainers = serde_json::from_str(json).expect("pass");
+        assert_eq!(c.option_cow, None);
+    }
+    // Validation applies whether borrowed or owned.
+    for json in [
+        r#"{"cow":"ABC","vec_cow":[],"hash_set_cow":[]}"#,
+        r#"{"cow":"\u0041BC","vec_cow":[],"hash_set_cow":[]}"#,
+        r#"{"cow":"abc","option_cow":"X","vec_cow":[],"hash_set_cow":[]}"#,
+        r#"{"cow":"abc","vec_cow":["abc","X"],"hash_set_cow":[]}"#,
+        r#"{"cow":"abc","vec_cow":[],"hash_set_cow":["X"]}"#,
+    ] {
+        let err = serde_json::from_str::<CowContainers>(json).expect_err("pass");
+        println!("serde_json::from_str err (expected): {}", err);
+    }
+}
 
-    I32String::try_from("").expect_err("pass");
-    I32String::try_from("abc").expect_err("pass");
-    I32String::try_from("12.34").expect_err("pass");
+#[derive(Debug, serde::Deserialize, Eq, PartialEq, serde::Serialize)]
+struct PlainContainers<'a> {
+    #[serde(borrow, with = "pneutype::serde::borrowed")]
+    borrowed: &'a LowercaseStr,
+    #[serde(borrow, default, with = "pneutype::serde::option_borrowed")]
+    option_borrowed: Option<&'a LowercaseStr>,
+    #[serde(borrow, with = "pneutype::serde::vec_borrowed")]
+    vec_borrowed: Vec<&'a LowercaseStr>,
+    #[serde(borrow, with = "pneutype::serde::hash_set_borrowed")]
+    hash_set_borrowed: HashSet<&'a LowercaseStr>,
+    // URL doesn't have the `deserialize` attribute, so it only deserializes via the `with`-modules.
+    #[serde(with = "pneutype::serde::owned")]
+    owned: URL,
+    #[serde(default, with = "pneutype::serde::option_owned")]
+    option_owned: Option<URL>,
+    #[serde(with = "pneutype::serde::vec_owned")]
+    vec_owned: Vec<URL>,
+    #[serde(with = "pneutype::serde::hash_set_owned")]
+    hash_set_owned: HashSet<Lowercase>,
+}
 
-    let mut i = I32String::try_from("123").expect("pass");
-    assert_eq!(i.as_pneu_str(), I32Str::new_ref("123").expect("pass"));
-    assert_eq!(i.as_str(), "123");
-    assert_eq!(i.to_value(), 123i32);
-    i.set_value(&456i32);
-    assert_eq!(i.as_pneu_str(), I32Str::new_ref("456").expect("pass"));
-    assert_eq!(i.as_str(), "456");
-    assert_eq!(i.to_value(), 456i32);
+#[test]
+fn test_pneu_str_serde_plain_containers() {
+    let json = r#"{"borrowed":"abc","option_borrowed":"def","vec_borrowed":["ghi"],"hash_set_borrowed":["jkl"],"owned":"http://a.com","option_owned":null,"vec_owned":["http://b.com","http://\u0063.com"],"hash_set_owned":["m\u006eo"]}"#;
+    let c: PlainContainers = serde_json::from_str(json).expect("pass");
+    assert_eq!(c.borrowed.as_str(), "abc");
+    assert_eq!(c.option_borrowed.map(LowercaseStr::as_str), Some("def"));
+    assert_eq!(c.vec_borrowed[0].as_str(), "ghi");
+    assert!(c
+        .hash_set_borrowed
+        .contains(LowercaseStr::new_ref("jkl").expect("pass")));
+    assert_eq!(c.owned.as_str(), "http://a.com");
+    assert_eq!(c.option_owned, None);
+    assert_eq!(c.vec_owned[1].as_str(), "http://c.com");
+    assert!(c.hash_set_owned.contains("mno"));
+    // Round-trip.
+    let c_json = serde_json::to_string(&c).expect("pass");
+    let c_deserialized: PlainContainers = serde_json::from_str(&c_json).expect("pass");
+    assert_eq!(c_deserialized, c);
+
+    // Validation applies, and borrowing escaped strings fails with an explanation.
+    let err = serde_json::from_str::<PlainContainers>(&json.replace("http://a.com", "a.com"))
+        .expect_err("pass");
+    println!("serde_json::from_str err (expected): {}", err);
+    assert!(err.to_string().contains("\"a.com\""));
+    let err = serde_json::from_str::<PlainContainers>(&json.replace("ghi", "g\\u0068i"))
+        .expect_err("pass");
+    println!("serde_json::from_str err (expected): {}", err);
+    assert!(err.to_string().contains("can't borrow \"ghi\""));
+}
 
-    type BoolString = ValueString<bool>;
-    type BoolStr = ValueStr<bool>;
+#[test]
+fn test_pneu_str_serde_seeds() {
+    use pneutype::serde::{CowSeed, OptionSeed, VecSeed};
+    use serde::de::DeserializeSeed;
+
+    let json = r#"[["abc",null],["d\u0065f"]]"#;
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let v = VecSeed(VecSeed(OptionSeed(CowSeed::<LowercaseStr>::new())))
+        .deserialize(&mut deserializer)
+        .expect("pass");
+    assert_eq!(v.len(), 2);
+    assert!(matches!(v[0][0], Some(Cow::Borrowed(_))));
+    assert_eq!(v[0][1], None);
+    assert!(matches!(v[1][0], Some(Cow::Owned(_))));
+    assert_eq!(v[1][0].as_deref().map(LowercaseStr::as_str), Some("def"));
+
+    // Errors read the same as those of the derived serde::Deserialize.
+    for json in [r#"["Abc"]"#, r#"["\u0041bc"]"#] {
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        let err = VecSeed(CowSeed::<LowercaseStr>::new())
+            .deserialize(&mut deserializer)
+            .expect_err("pass");
+        let derived_err = serde_json::from_str::<Vec<&LowercaseStr>>(json).expect_err("pass");
+        assert_eq!(err.to_string(), derived_err.to_string());
+    }
+    let json = r#"["x"]"#;
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let err = VecSeed(pneutype::serde::OwnedSeed::<ValueString<i32>>::new())
+        .deserialize(&mut deserializer)
+        .expect_err("pass");
+    let derived_err = serde_json::from_str::<Vec<ValueString<i32>>>(json).expect_err("pass");
+    assert_eq!(err.to_string(), derived_err.to_string());
+
+    // Ordinary Deserialize types compose via PhantomData.
+    let json = r#"[["abc"],["def"]]"#;
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let v = VecSeed(std::marker::PhantomData::<Vec<&LowercaseStr>>)
+        .deserialize(&mut deserializer)
+        .expect("pass");
+    assert_eq!(v[1][0].as_str(), "def");
+}
 
-    BoolString::try_from("").expect_err("pass");
-    BoolString::try_from("blah").expect_err("pass");
-    BoolString::try_from("1").expect_err("pass");
+/// A tenant name is non-empty and ASCII-alphanumeric, and depending on the deployment, may also be subject to a
+/// max length and/or an allow-list.
+#[derive(Debug, Eq, PartialEq, pneutype::PneuString)]
+#[pneu_string(borrow = "TenantNameStr", no_arbitrary)]
+struct TenantName(String);
 
-    let mut i = BoolString::try_from("true").expect("pass");
-    assert_eq!(i.as_pneu_str(), BoolStr::new_ref("true").expect("pass"));
-    assert_eq!(i.as_str(), "true");
-    assert_eq!(i.to_value(), true);
-    i.set_value(&false);
-    assert_eq!(i.as_pneu_str(), BoolStr::new_ref("false").expect("pass"));
-    assert_eq!(i.as_str(), "false");
-    assert_eq!(i.to_value(), false);
+#[derive(Debug, Eq, PartialEq, pneutype::PneuStr)]
+#[repr(transparent)]
+struct TenantNameStr(str);
+
+impl pneutype::Validate for TenantNameStr {
+    type Data = str;
+    type Error = &'static str;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        if data.is_empty() {
+            Err("must be non-empty")
+        } else if !data.chars().all(|c| c.is_ascii_alphanumeric()) {
+            Err("must be ASCII-alphanumeric")
+        } else {
+            Ok(())
+        }
+    }
 }
 
-#[test]
-fn test_pneu_string_with_generics_serde() {
-    type I32String = ValueString<i32>;
-    type I32Str = ValueStr<i32>;
+struct MaxLen(usize);
 
-    {
-        let i = I32String::try_from("123").expect("pass");
-        println!("i: {:?}", i);
-        assert_eq!(i.as_pneu_str(), I32Str::new_ref("123").expect("pass"));
-        let i_json = serde_json::to_string(&i).expect("pass");
-        println!("i_json: {}", i_json);
-        assert_eq!(i_json, "\"123\"");
-        let i_deserialized: I32String = serde_json::from_str(&i_json).expect("pass");
-        println!("i_deserialized: {:?}", i_deserialized);
-        assert_eq!(i_deserialized, i);
+impl pneutype::ValidateWith<MaxLen> for TenantNameStr {
+    fn validate_with(data: &Self::Data, ctx: &MaxLen) -> Result<(), Self::Error> {
+        if data.len() > ctx.0 {
+            Err("too long")
+        } else {
+            Ok(())
+        }
     }
+}
 
-    {
-        let i = I32Str::new_ref("123").expect("pass");
-        println!("i: {:?}", i);
-        let i_json = serde_json::to_string(&i).expect("pass");
-        println!("i_json: {}", i_json);
-        assert_eq!(i_json, "\"123\"");
-        let i_deserialized: &I32Str = serde_json::from_str(&i_json).expect("pass");
-        println!("i_deserialized: {:?}", i_deserialized);
-        assert_eq!(i_deserialized, i);
+struct AllowList(HashSet<&'static str>);
+
+impl pneutype::ValidateWith<AllowList> for TenantNameStr {
+    fn validate_with(data: &Self::Data, ctx: &AllowList) -> Result<(), Self::Error> {
+        if ctx.0.contains(data) {
+            Ok(())
+        } else {
+            Err("not in the allow-list")
+        }
     }
 }
 
 #[test]
-fn test_pneu_str_trait() {
-    let x = <LowercaseStr as pneutype::PneuStr>::new_ref("abcxyz").expect("pass");
-    assert_eq!(<LowercaseStr as pneutype::AsStr>::as_str(x), "abcxyz");
+fn test_pneu_string_and_pneu_str_validate_with() {
+    let max_len = MaxLen(4);
+    let allow_list = AllowList(["acme", "initech"].into_iter().collect());
+
+    TenantNameStr::new_ref_with("acme", &max_len).expect("pass");
+    TenantNameStr::new_ref_with("initech", &allow_list).expect("pass");
+    assert_eq!(
+        TenantNameStr::new_ref_with("initech", &max_len),
+        Err("too long")
+    );
+    assert_eq!(
+        TenantNameStr::new_ref_with("globex", &allow_list),
+        Err("not in the allow-list")
+    );
+    // The context-independent constraint is always checked first.
+    assert_eq!(
+        TenantNameStr::new_ref_with("", &max_len),
+        Err("must be non-empty")
+    );
+    assert_eq!(
+        TenantNameStr::new_ref_with("a-b", &allow_list),
+        Err("must be ASCII-alphanumeric")
+    );
 
-    let y = unsafe { <LowercaseStr as pneutype::NewRefUnchecked>::new_ref_unchecked("abcpqr") };
-    assert_eq!(<LowercaseStr as pneutype::AsStr>::as_str(y), "abcpqr");
+    let t = TenantName::try_from_with("acme", &max_len).expect("pass");
+    assert_eq!(t.as_str(), "acme");
+    let t = TenantName::try_from_with("initech".to_string(), &allow_list).expect("pass");
+    assert_eq!(t.as_str(), "initech");
+    assert_eq!(
+        TenantName::try_from_with("initech", &max_len),
+        Err("too long")
+    );
+    assert_eq!(
+        <TenantName as pneutype::PneuString>::try_from_with("globex", &allow_list),
+        Err("not in the allow-list")
+    );
+    assert_eq!(
+        <TenantNameStr as pneutype::PneuStr>::new_ref_with("", &allow_list),
+        Err("must be non-empty")
+    );
 }
 
-fn test_pneu_str_trait_case<T>(valid_str: &str, invalid_str: &str)
-where
-    T: std::fmt::Debug + pneutype::PneuStr + ?Sized,
-{
-    let x = T::new_ref(valid_str).expect("pass");
-    assert_eq!(x.as_str(), valid_str);
-
-    T::new_ref(invalid_str).expect_err("pass");
+#[test]
+fn test_pneu_string_and_pneu_str_validate_with_serde() {
+    use pneutype::serde::{BorrowedContextSeed, ContextSeed, VecSeed};
+    use serde::de::DeserializeSeed;
+
+    let max_len = MaxLen(4);
+    let allow_list = AllowList(["acme", "initech"].into_iter().collect());
+
+    let mut deserializer = serde_json::Deserializer::from_str(r#""acme""#);
+    let t = ContextSeed::<TenantName, _>::new(&max_len)
+        .deserialize(&mut deserializer)
+        .expect("pass");
+    assert_eq!(t.as_str(), "acme");
+
+    let mut deserializer = serde_json::Deserializer::from_str(r#""initech""#);
+    let err = ContextSeed::<TenantName, _>::new(&max_len)
+        .deserialize(&mut deserializer)
+        .expect_err("pass");
+    println!("ContextSeed::deserialize err (expected): {}", err);
+    assert!(err.to_string().contains("too long"));
+
+    let mut deserializer = serde_json::Deserializer::from_str(r#"["initech","acme"]"#);
+    let v = VecSeed(BorrowedContextSeed::<TenantNameStr, _>::new(&allow_list))
+        .deserialize(&mut deserializer)
+        .expect("pass");
+    assert_eq!(
+        v.iter().map(|t| t.as_str()).collect::<Vec<_>>(),
+        vec!["initech", "acme"]
+    );
 
-    let y = unsafe { T::new_ref_unchecked(valid_str) };
-    assert_eq!(y.as_str(), valid_str);
-    assert_eq!(<T as AsRef<str>>::as_ref(&y), valid_str);
+    let mut deserializer = serde_json::Deserializer::from_str(r#"["initech","globex"]"#);
+    let err = VecSeed(BorrowedContextSeed::<TenantNameStr, _>::new(&allow_list))
+        .deserialize(&mut deserializer)
+        .expect_err("pass");
+    println!("VecSeed::deserialize err (expected): {}", err);
+    assert!(err.to_string().contains("\"globex\""));
+
+    // Strings that can't be borrowed are validated, and then rejected with an explanation.
+    let mut deserializer = serde_json::Deserializer::from_str(r#"["\u0061cme"]"#);
+    let err = VecSeed(BorrowedContextSeed::<TenantNameStr, _>::new(&allow_list))
+        .deserialize(&mut deserializer)
+        .expect_err("pass");
+    println!("VecSeed::deserialize err (expected): {}", err);
+    assert!(err.to_string().contains("can't borrow \"acme\""));
+    let mut deserializer = serde_json::Deserializer::from_str(r#"["gl\u006fbex"]"#);
+    let err = VecSeed(BorrowedContextSeed::<TenantNameStr, _>::new(&allow_list))
+        .deserialize(&mut deserializer)
+        .expect_err("pass");
+    println!("VecSeed::deserialize err (expected): {}", err);
+    assert!(err.to_string().contains("not in the allow-list"));
 }
 
-fn test_pneu_string_trait_case<T>(valid_str: &str, invalid_str: &str)
-where
-    T: std::fmt::Debug + pneutype::PneuString,
-    T::Borrowed: std::fmt::Debug,
-{
-    let x = T::try_from(valid_str.to_string()).expect("pass");
-    assert_eq!(x.as_str(), valid_str);
-    let x_string = x.into_string();
-    assert_eq!(x_string.as_str(), valid_str);
+/// A service name is non-empty lowercase ASCII-alphanumeric words separated by hyphens.  Service names used to
+/// allow uppercase and underscores, and these legacy forms are canonicalized into the strict form.
+#[derive(Debug, Eq, PartialEq, pneutype::PneuString, serde::Serialize)]
+#[pneu_string(borrow = "ServiceNameStr", deserialize = "lenient", no_arbitrary)]
+struct ServiceName(String);
 
-    T::try_from(invalid_str.to_string()).expect_err("pass");
-    T::from_str(invalid_str).expect_err("pass");
+#[derive(Debug, Eq, PartialEq, pneutype::PneuStr, serde::Serialize)]
+#[pneu_str(deserialize = "strict")]
+#[repr(transparent)]
+struct ServiceNameStr(str);
 
-    let y = unsafe { T::new_unchecked(valid_str.to_string()) };
-    assert_eq!(y.as_str(), valid_str);
-    let y_ref = <T as AsRef<T::Borrowed>>::as_ref(&y);
-    {
-        use pneutype::AsStr;
-        assert_eq!(y_ref.as_str(), valid_str);
+impl pneutype::Validate for ServiceNameStr {
+    type Data = str;
+    type Error = &'static str;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        if data.is_empty() {
+            Err("must be non-empty")
+        } else if !data
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            Err("must be lowercase ASCII-alphanumeric or '-'")
+        } else {
+            Ok(())
+        }
     }
-    assert_eq!(<T as AsRef<str>>::as_ref(&y), valid_str);
+}
 
-    let z = y.as_pneu_str();
-    {
-        use pneutype::AsStr;
-        assert_eq!(z.as_str(), valid_str);
+impl pneutype::ValidateLenient for ServiceNameStr {
+    fn validate_lenient(data: &str) -> Result<(), Self::Error> {
+        if data.is_empty() {
+            Err("must be non-empty")
+        } else if !data
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            Err("must be ASCII-alphanumeric, '-' or '_'")
+        } else {
+            Ok(())
+        }
+    }
+    fn canonicalize(data: &str) -> Cow<'_, str> {
+        if data.chars().any(|c| c.is_ascii_uppercase() || c == '_') {
+            Cow::Owned(data.to_ascii_lowercase().replace('_', "-"))
+        } else {
+            Cow::Borrowed(data)
+        }
     }
+}
 
-    test_pneu_str_trait_case::<T::Borrowed>(valid_str, invalid_str);
+#[derive(Debug, serde::Deserialize, PartialEq)]
+struct ServiceConfig {
+    #[serde(with = "pneutype::serde::lenient")]
+    name: Lowercase,
+    service: ServiceName,
 }
 
 #[test]
-fn test_pneu_string_trait_lowercase() {
-    test_pneu_string_trait_case::<Lowercase>("abcxyz", "abcPqr");
+fn test_pneu_string_validate_lenient() {
+    // Strict construction still rejects legacy forms.
+    assert!(ServiceName::try_from("Billing_API").is_err());
+
+    let s = ServiceName::from_legacy("Billing_API").expect("pass");
+    assert_eq!(s.as_str(), "billing-api");
+    let s = ServiceName::from_legacy("billing-api".to_string()).expect("pass");
+    assert_eq!(s.as_str(), "billing-api");
+    assert_eq!(ServiceName::from_legacy(""), Err("must be non-empty"));
+    assert_eq!(
+        ServiceName::from_legacy("billing api"),
+        Err("must be ASCII-alphanumeric, '-' or '_'")
+    );
+    let s = <ServiceName as pneutype::PneuString>::from_legacy("Billing").expect("pass");
+    assert_eq!(s.as_str(), "billing");
 }
 
 #[test]
-fn test_pneu_string_trait_value_string_i32() {
-    test_pneu_string_trait_case::<ValueString<i32>>("123", "345.6");
+fn test_pneu_string_validate_lenient_serde() {
+    let s = serde_json::from_str::<ServiceName>(r#""Billing_API""#).expect("pass");
+    assert_eq!(s.as_str(), "billing-api");
+    // Serializes in the strict form, which round-trips.
+    assert_eq!(serde_json::to_string(&s).expect("pass"), r#""billing-api""#);
+    let err = serde_json::from_str::<ServiceName>(r#""billing api""#).expect_err("pass");
+    assert_eq!(
+        err.to_string(),
+        r#"invalid value: string "billing api", expected a ServiceName (must be ASCII-alphanumeric, '-' or '_') at line 1 column 13"#
+    );
+    // The PneuStr's deserialize is strict, since it can't canonicalize in place.
+    assert!(serde_json::from_str::<&ServiceNameStr>(r#""Billing_API""#).is_err());
+    serde_json::from_str::<&ServiceNameStr>(r#""billing-api""#).expect("pass");
+
+    // Lenient deserialization can also be opted into per field.
+    let config =
+        serde_json::from_str::<ServiceConfig>(r#"{"name":"Billing","service":"Billing_API"}"#)
+            .expect("pass");
+    assert_eq!(config.name.as_str(), "billing");
+    assert_eq!(config.service.as_str(), "billing-api");
+    // Whereas Lowercase's own deserialize is strict.
+    assert!(serde_json::from_str::<Lowercase>(r#""Billing""#).is_err());
+    assert!(
+        serde_json::from_str::<ServiceConfig>(r#"{"name":"Bill1ng","service":"billing"}"#).is_err()
+    );
+}
+
+/// A hostname is non-empty lowercase ASCII-alphanumeric labels separated by '.', and is normalized by trimming
+/// surrounding whitespace, lowercasing, and dropping a trailing '.'.
+#[derive(Debug, Eq, PartialEq, pneutype::PneuString, serde::Serialize)]
+#[pneu_string(borrow = "HostnameStr", deserialize = "normalize", no_arbitrary)]
+struct Hostname(String);
+
+#[derive(Debug, Eq, PartialEq, pneutype::PneuStr, serde::Serialize)]
+#[pneu_str(deserialize)]
+#[repr(transparent)]
+struct HostnameStr(str);
+
+impl pneutype::Validate for HostnameStr {
+    type Data = str;
+    type Error = &'static str;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        if data.split('.').any(|label| {
+            label.is_empty()
+                || !label
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        }) {
+            Err("must be '.'-separated non-empty lowercase ASCII-alphanumeric labels")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl pneutype::Normalize for HostnameStr {
+    fn normalize(data: Cow<'_, str>) -> Cow<'_, str> {
+        let trimmed = data.trim();
+        let trimmed = trimmed.strip_suffix('.').unwrap_or(trimmed);
+        if trimmed.len() == data.len() && !data.chars().any(|c| c.is_ascii_uppercase()) {
+            data
+        } else {
+            Cow::Owned(trimmed.to_ascii_lowercase())
+        }
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Endpoint {
+    #[serde(with = "pneutype::serde::normalize")]
+    host: Hostname,
+    #[serde(with = "pneutype::serde::lenient")]
+    owner: Lowercase,
 }
 
 #[test]
-fn test_pneu_string_trait_value_string_f32() {
-    test_pneu_string_trait_case::<ValueString<f32>>("12.25", "abc");
+fn test_pneu_string_normalize() {
+    // Validation stays strict.
+    assert!(Hostname::try_from("Example.COM.").is_err());
+    assert!(HostnameStr::new_ref(" example.com").is_err());
+
+    let h = Hostname::normalize_from(" Example.COM. ").expect("pass");
+    assert_eq!(h.as_str(), "example.com");
+    let h = Hostname::try_normalize("example.com".to_string()).expect("pass");
+    assert_eq!(h.as_str(), "example.com");
+    let h =
+        <Hostname as pneutype::PneuString>::try_normalize("EXAMPLE.com".to_string()).expect("pass");
+    assert_eq!(h.as_str(), "example.com");
+    assert_eq!(
+        Hostname::normalize_from("example..com"),
+        Err("must be '.'-separated non-empty lowercase ASCII-alphanumeric labels")
+    );
 }
 
 #[test]
-fn test_pneu_str_trait_value_string_f32() {
-    test_pneu_str_trait_case::<FreeStandingStr>("blah", "");
+fn test_pneu_string_normalize_serde() {
+    let h = serde_json::from_str::<Hostname>(r#"" Example.COM.""#).expect("pass");
+    assert_eq!(h.as_str(), "example.com");
+    // Escape sequences make serde_json unescape into a scratch buffer.
+    let h = serde_json::from_str::<Hostname>(r#""\tExample.COM""#).expect("pass");
+    assert_eq!(h.as_str(), "example.com");
+    let err = serde_json::from_str::<Hostname>(r#""exa mple.com""#).expect_err("pass");
+    assert_eq!(
+        err.to_string(),
+        r#"invalid value: string "exa mple.com", expected a Hostname (must be '.'-separated non-empty lowercase ASCII-alphanumeric labels) at line 1 column 14"#
+    );
+    // An owned String is normalized in place, so the error can't quote it.
+    {
+        use serde::{
+            de::{DeserializeSeed, IntoDeserializer},
+            Deserialize,
+        };
+        let deserializer =
+            |s: &str| -> serde::de::value::StringDeserializer<serde::de::value::Error> {
+                s.to_string().into_deserializer()
+            };
+        let h = Hostname::deserialize(deserializer(" Example.COM.")).expect("pass");
+        assert_eq!(h.as_str(), "example.com");
+        let h = pneutype::serde::NormalizeSeed::<Hostname>::new()
+            .deserialize(deserializer(" Example.COM."))
+            .expect("pass");
+        assert_eq!(h.as_str(), "example.com");
+        let expected = "invalid value: string, expected a Hostname (must be '.'-separated non-empty lowercase ASCII-alphanumeric labels)";
+        let err = Hostname::deserialize(deserializer(" Exa mple.COM")).expect_err("pass");
+        assert_eq!(err.to_string(), expected);
+        let err = pneutype::serde::NormalizeSeed::<Hostname>::new()
+            .deserialize(deserializer(" Exa mple.COM"))
+            .expect_err("pass");
+        assert_eq!(err.to_string(), expected);
+    }
+    // The PneuStr's deserialize is strict, since it can't normalize in place.
+    assert!(serde_json::from_str::<&HostnameStr>(r#""Example.com""#).is_err());
+
+    let endpoint =
+        serde_json::from_str::<Endpoint>(r#"{"host":"Example.COM.","owner":"Ops"}"#).expect("pass");
+    assert_eq!(endpoint.host.as_str(), "example.com");
+    assert_eq!(endpoint.owner.as_str(), "ops");
+}
+
+/// A slug is 1 to 12 lowercase ASCII-alphanumeric chars or '-', and titles can be sanitized into slugs.
+#[derive(Debug, Eq, PartialEq, pneutype::PneuString)]
+#[pneu_string(borrow = "SlugStr", no_arbitrary)]
+struct Slug(String);
+
+#[derive(Debug, Eq, PartialEq, pneutype::PneuStr)]
+#[repr(transparent)]
+struct SlugStr(str);
+
+impl pneutype::Validate for SlugStr {
+    type Data = str;
+    type Error = &'static str;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        if data.is_empty() {
+            Err("must be non-empty")
+        } else if data.chars().count() > 12 {
+            Err("must be at most 12 chars")
+        } else if !data
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            Err("must be lowercase ASCII-alphanumeric or '-'")
+        } else {
+            Ok(())
+        }
+    }
 }
+
+impl pneutype::Sanitize for SlugStr {
+    fn sanitize<'a>(data: &'a str, report: &mut pneutype::SanitizeReport) -> Cow<'a, str> {
+        if <Self as pneutype::Validate>::validate(data).is_ok() {
+            return Cow::Borrowed(data);
+        }
+        if data.is_empty() {
+            report.record(0..0, "-", "empty");
+            return Cow::Borrowed("-");
+        }
+        let data = report.truncate_chars(data, 12, "too long");
+        let data = report.replace_chars(
+            &data,
+            |c| !(c.is_ascii_alphanumeric() || c == '-'),
+            '-',
+            "forbidden char",
+        );
+        let mut sanitized = String::with_capacity(data.len());
+        for (i, c) in data.char_indices() {
+            if c.is_ascii_uppercase() {
+                report.record(i..i + 1, c.to_ascii_lowercase(), "uppercase");
+            }
+            sanitized.push(c.to_ascii_lowercase());
+        }
+        Cow::Owned(sanitized)
+    }
+}
+
+#[test]
+fn test_pneu_string_sanitize() {
+    assert!(Slug::try_from("Hello, World!").is_err());
+
+    let (slug, report) = Slug::sanitize_with_report("Hello, World!");
+    assert_eq!(slug.as_str(), "hello--world");
+    assert_eq!(
+        report.changes(),
+        &[
+            pneutype::SanitizeChange {
+                range: 12..13,
+                replacement: String::new(),
+                reason: "too long",
+            },
+            pneutype::SanitizeChange {
+                range: 5..6,
+                replacement: "-".to_string(),
+                reason: "forbidden char",
+            },
+            pneutype::SanitizeChange {
+                range: 6..7,
+                replacement: "-".to_string(),
+                reason: "forbidden char",
+            },
+            pneutype::SanitizeChange {
+                range: 0..1,
+                replacement: "h".to_string(),
+                reason: "uppercase",
+            },
+            pneutype::SanitizeChange {
+                range: 7..8,
+                replacement: "w".to_string(),
+                reason: "uppercase",
+            },
+        ]
+    );
+    assert!(!report.is_unchanged());
+    // Each change's range is relative to the input of the step that made it.
+    let (slug, report) = Slug::sanitize_with_report("ééééééééééééé!A");
+    assert_eq!(slug.as_str(), "------------");
+    assert_eq!(report.changes()[0].range, 24..28);
+    assert_eq!(report.changes()[1].range, 0..2);
+    assert_eq!(report.changes().len(), 13);
+
+    let (slug, report) = Slug::sanitize_with_report("Already-OK");
+    assert_eq!(slug.as_str(), "already-ok");
+    assert_eq!(report.changes().len(), 3);
+
+    let (slug, report) = Slug::sanitize_with_report("already-ok");
+    assert_eq!(slug.as_str(), "already-ok");
+    assert!(report.is_unchanged());
+
+    assert_eq!(Slug::sanitize("").as_str(), "-");
+    // Truncation happens on a char boundary, before the multi-byte chars are replaced.
+    assert_eq!(
+        <Slug as pneutype::PneuString>::sanitize("ééééééééééééé").as_str(),
+        "------------"
+    );
+
+    let mut report = pneutype::SanitizeReport::new();
+    assert_eq!(report.truncate_bytes("aé", 2, "too long"), "a");
+    assert_eq!(report.changes()[0].range, 1..3);
+}
+
+const ABC: &LowercaseStr = pneutype::pneu_str!(LowercaseStr, "abc");
+static XYZ: &LowercaseStr = pneutype::pneu_str!(LowercaseStr, "xyz");
+
+#[test]
+fn test_pneu_str_literal() {
+    assert_eq!(ABC.as_str(), "abc");
+    assert_eq!(XYZ.count_lowercase_chars(), 3);
+    assert_eq!(
+        pneutype::pneu_str!(LowercaseStr, ""),
+        LowercaseStr::new_ref("").expect("pass")
+    );
+    let t = Lowercase::from(ABC);
+    assert_eq!(t.as_str(), "abc");
+    match LowercaseStr::new_ref("xyz").expect("pass") {
+        s if s == ABC => panic!("shouldn't match"),
+        s if s == XYZ => {}
+        _ => panic!("should have matched"),
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, pneutype::PneuString)]
+#[pneu_string(
+    borrow = "RoleStr",
+    consts(DEFAULT, ADMIN),
+    default = "DEFAULT",
+    no_arbitrary
+)]
+struct Role(String);
+
+#[derive(Debug, Eq, PartialEq, pneutype::PneuStr)]
+#[pneu_str(
+    consts(DEFAULT = "default", ADMIN = "admin"),
+    validate_const,
+    default = "DEFAULT"
+)]
+#[repr(transparent)]
+struct RoleStr(str);
+
+impl RoleStr {
+    const fn validate_const(s: &str) -> Result<(), &'static str> {
+        if s.is_empty() {
+            return Err("must be non-empty");
+        }
+        let bytes = s.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            if !bytes[i].is_ascii_lowercase() {
+                return Err("must be all-lowercase ASCII");
+            }
+            i += 1;
+        }
+        Ok(())
+    }
+}
+
+impl pneutype::Validate for RoleStr {
+    type Data = str;
+    type Error = &'static str;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        Self::validate_const(data)
+    }
+}
+
+/// Consts are validated at compile time, even where the validate_const is a thin wrapper.
+#[derive(Debug, pneutype::PneuStr)]
+#[pneu_str(consts(FOO = "foo", BAR = "bar"), validate_const)]
+#[repr(transparent)]
+struct FooBarStr(str);
+
+impl FooBarStr {
+    const fn validate_const(s: &str) -> Result<(), &'static str> {
+        if s.len() == 3 {
+            Ok(())
+        } else {
+            Err("must have length 3")
+        }
+    }
+}
+
+impl pneutype::Validate for FooBarStr {
+    type Data = str;
+    type Error = &'static str;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        Self::validate_const(data)
+    }
+}
+
+#[test]
+fn test_pneu_str_and_pneu_string_consts() {
+    const ADMIN: &RoleStr = RoleStr::ADMIN;
+    assert_eq!(ADMIN.as_str(), "admin");
+    assert_eq!(RoleStr::DEFAULT, RoleStr::new_ref("default").expect("pass"));
+    assert_eq!(<&RoleStr>::default(), RoleStr::DEFAULT);
+    assert_eq!(FooBarStr::FOO.as_str(), "foo");
+    assert_eq!(FooBarStr::BAR.as_str(), "bar");
+
+    assert_eq!(Role::admin_value().as_str(), "admin");
+    assert_eq!(Role::default_value().as_str(), "default");
+    assert_eq!(Role::default(), Role::default_value());
+    assert_eq!(Role::default().as_pneu_str(), RoleStr::DEFAULT);
+}
+
+#[derive(Debug, Eq, PartialEq, pneutype::PneuString, serde::Serialize)]
+#[pneu_string(borrow = "EnvironmentStr", deserialize, consts(DEV))]
+struct Environment(String);
+
+#[derive(Debug, Eq, PartialEq, pneutype::PneuStr, serde::Serialize)]
+#[pneu_str(deserialize, one_of("dev", "staging", "prod", "us-east-1"))]
+#[repr(transparent)]
+struct EnvironmentStr(str);
+
+#[derive(Debug, pneutype::PneuStr)]
+#[pneu_str(one_of("debug", "info", "warn"), enum_name = "Level", default = "INFO")]
+#[repr(transparent)]
+struct LogLevelStr(str);
+
+#[test]
+fn test_pneu_str_one_of() {
+    assert_eq!(EnvironmentStr::DEV.as_str(), "dev");
+    assert_eq!(EnvironmentStr::US_EAST_1.as_str(), "us-east-1");
+    EnvironmentStr::new_ref("staging").expect("pass");
+    assert_eq!(
+        EnvironmentStr::new_ref("qa"),
+        Err(r#"EnvironmentStr must be one of "dev", "staging", "prod", "us-east-1""#)
+    );
+    assert_eq!(
+        EnvironmentStr::all()
+            .map(|e| e.as_str())
+            .collect::<Vec<_>>(),
+        vec!["dev", "staging", "prod", "us-east-1"]
+    );
+    assert_eq!(
+        pneutype::pneu_str!(EnvironmentStr, "prod"),
+        EnvironmentStr::PROD
+    );
+
+    // Exhaustive matching via the generated enum.
+    for e in EnvironmentStr::all() {
+        let is_production = match e.kind() {
+            EnvironmentKind::Dev | EnvironmentKind::Staging => false,
+            EnvironmentKind::Prod | EnvironmentKind::UsEast1 => true,
+        };
+        assert_eq!(
+            is_production,
+            e.as_str() != "dev" && e.as_str() != "staging"
+        );
+        assert_eq!(<&EnvironmentStr>::from(e.kind()), e);
+        assert_eq!(EnvironmentKind::from(e), e.kind());
+    }
+    assert_eq!(EnvironmentKind::ALL.len(), 4);
+    assert_eq!(EnvironmentKind::UsEast1.to_string(), "us-east-1");
+    assert_eq!(
+        EnvironmentKind::from_str("staging"),
+        Ok(EnvironmentKind::Staging)
+    );
+    assert!(EnvironmentKind::from_str("qa").is_err());
+
+    assert_eq!(
+        Environment::dev_value().as_pneu_str().kind(),
+        EnvironmentKind::Dev
+    );
+    assert_eq!(<&LogLevelStr>::default().kind(), Level::Info);
+    assert_eq!(Level::Warn.as_pneu_str().as_str(), "warn");
+
+    // Values stay in the zero-copy form for serde.
+    let v: Vec<&EnvironmentStr> = serde_json::from_str(r#"["prod","dev"]"#).expect("pass");
+    assert_eq!(v, vec![EnvironmentStr::PROD, EnvironmentStr::DEV]);
+    assert_eq!(
+        serde_json::to_string(&v).expect("pass"),
+        r#"["prod","dev"]"#
+    );
+    assert!(serde_json::from_str::<Environment>(r#""qa""#).is_err());
+}
+
+/// A handle is a LowercaseStr of 3 to 8 chars.
+#[derive(Debug, Eq, PartialEq, pneutype::PneuString)]
+#[pneu_string(borrow = "HandleStr", refines = "Lowercase", no_arbitrary)]
+struct Handle(String);
+
+#[derive(Debug, Eq, PartialEq, pneutype::PneuStr)]
+#[pneu_str(refines = "LowercaseStr")]
+#[repr(transparent)]
+struct HandleStr(str);
+
+impl pneutype::Refines<LowercaseStr> for HandleStr {
+    type Error = &'static str;
+    fn validate_refinement(data: &str) -> Result<(), Self::Error> {
+        if (3..=8).contains(&data.len()) {
+            Ok(())
+        } else {
+            Err("must be 3 to 8 chars")
+        }
+    }
+}
+
+/// A tag is a non-empty LowercaseStr, declared by wrapping LowercaseStr directly.
+#[derive(Debug, Eq, PartialEq, pneutype::PneuStr)]
+#[pneu_str(refines = "LowercaseStr")]
+#[repr(transparent)]
+struct TagStr(LowercaseStr);
+
+impl pneutype::Refines<LowercaseStr> for TagStr {
+    type Error = String;
+    fn validate_refinement(data: &str) -> Result<(), Self::Error> {
+        if data.is_empty() {
+            Err("must be non-empty".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn count_lowercase(s: impl AsRef<LowercaseStr>) -> usize {
+    s.as_ref().count_lowercase_chars()
+}
+
+#[test]
+fn test_pneu_str_and_pneu_string_refines() {
+    // The parent's constraint is checked first.
+    assert_eq!(
+        HandleStr::new_ref("Bob"),
+        Err("must be an all-lowercase string")
+    );
+    assert_eq!(HandleStr::new_ref("bo"), Err("must be 3 to 8 chars"));
+    let h = HandleStr::new_ref("bob").expect("pass");
+
+    // Upward conversions.
+    let l: &LowercaseStr = h.into();
+    assert_eq!(l.as_str(), "bob");
+    assert_eq!(count_lowercase(h), 3);
+
+    // Checked downward conversions.
+    let l = LowercaseStr::new_ref("alice").expect("pass");
+    let h = <&HandleStr>::try_from(l).expect("pass");
+    assert_eq!(h.as_str(), "alice");
+    let l = LowercaseStr::new_ref("bo").expect("pass");
+    assert_eq!(<&HandleStr>::try_from(l), Err("must be 3 to 8 chars"));
+
+    // The same with the inner-field form.
+    assert_eq!(
+        TagStr::new_ref("Tag"),
+        Err("must be an all-lowercase string".to_string())
+    );
+    assert_eq!(TagStr::new_ref(""), Err("must be non-empty".to_string()));
+    let t = TagStr::new_ref("tag").expect("pass");
+    assert_eq!(t.as_str(), "tag");
+    assert_eq!(<&LowercaseStr>::from(t).as_str(), "tag");
+    assert_eq!(count_lowercase(t), 3);
+
+    // Owned conversions.
+    let h = Handle::try_from("carol").expect("pass");
+    assert_eq!(count_lowercase(h.as_pneu_str()), 5);
+    let l = Lowercase::from(h);
+    assert_eq!(l.as_str(), "carol");
+    let h = Handle::try_from(l).expect("pass");
+    assert_eq!(h.as_str(), "carol");
+    assert_eq!(
+        Handle::try_from(Lowercase::try_from("carolinex").expect("pass")),
+        Err("must be 3 to 8 chars")
+    );
+}
+
+mod validated {
+    use pneutype::{
+        validators::{
+            And, AsciiDigit, AsciiLowercase, CharIs, CharOr, CharsAll, LengthError, MaxLen, MinLen,
+            Not, Or,
+        },
+        ValidatedStr, ValidatedString,
+    };
+
+    type SlugRule = And<MinLen<1>, And<MaxLen<8>, CharsAll<CharOr<AsciiLowercase, CharIs<'-'>>>>>;
+    type SlugStr = ValidatedStr<SlugRule>;
+    type Slug = ValidatedString<SlugRule>;
+    type DigitsOrEmpty = ValidatedStr<Or<CharsAll<AsciiDigit>, MaxLen<0>>>;
+    type NotDigits = ValidatedStr<Not<CharsAll<AsciiDigit>>>;
+
+    #[derive(Debug, serde::Deserialize, PartialEq, serde::Serialize)]
+    struct Post<'a> {
+        #[serde(borrow)]
+        slug: &'a SlugStr,
+        slugs: Vec<Slug>,
+    }
+
+    #[test]
+    fn test_validated_str_and_string() {
+        let s = SlugStr::new_ref("a-slug").expect("pass");
+        assert_eq!(s.as_str(), "a-slug");
+        assert_eq!(
+            SlugStr::new_ref("").expect_err("pass").to_string(),
+            "must be at least 1 byte long, but was 0"
+        );
+        assert_eq!(
+            SlugStr::new_ref("a-long-slug")
+                .expect_err("pass")
+                .to_string(),
+            "must be at most 8 bytes long, but was 11"
+        );
+        assert_eq!(
+            SlugStr::new_ref("a_slug").expect_err("pass").to_string(),
+            "invalid char '_' at byte 1 (expected ASCII lowercase or '-')"
+        );
+        assert!(matches!(
+            SlugStr::new_ref(""),
+            Err(pneutype::validators::AndError::Left(
+                LengthError::TooShort {
+                    min: 1,
+                    actual: 0,
+                    unit: "bytes"
+                }
+            ))
+        ));
+
+        DigitsOrEmpty::new_ref("123").expect("pass");
+        DigitsOrEmpty::new_ref("").expect("pass");
+        assert_eq!(
+            DigitsOrEmpty::new_ref("12a").expect_err("pass").to_string(),
+            "invalid char 'a' at byte 2 (expected ASCII digit), and must be at most 0 bytes long, but was 3"
+        );
+        NotDigits::new_ref("12a").expect("pass");
+        assert!(NotDigits::new_ref("123").is_err());
+
+        // Weakening is free, and is checked at compile time.
+        let w: &ValidatedStr<MinLen<1>> = s.weaken();
+        assert_eq!(w.as_str(), "a-slug");
+        let w: &ValidatedStr<MaxLen<8>> = s.weaken();
+        assert_eq!(w.as_str(), "a-slug");
+        let w: &ValidatedStr<Or<MaxLen<8>, CharsAll<AsciiDigit>>> = w.weaken();
+        assert_eq!(w.as_str(), "a-slug");
+
+        let t = Slug::try_from("slug").expect("pass");
+        assert_eq!(t.as_pneu_str(), SlugStr::new_ref("slug").expect("pass"));
+        assert_eq!(t.clone().weaken::<SlugRule, _>(), t);
+        let t: ValidatedString<MaxLen<8>> = t.weaken();
+        assert_eq!(t.as_str(), "slug");
+
+        // The validators with an obvious repair strategy implement Sanitize.
+        let (t, report) =
+            ValidatedString::<And<MaxLen<8>, CharsAll<AsciiLowercase>>>::sanitize_with_report(
+                "Hello_World!",
+            );
+        // The forbidden chars are filtered out before truncating, even though MaxLen comes first.
+        assert_eq!(t.as_str(), "elloorld");
+        assert_eq!(report.changes().len(), 4);
+    }
+
+    #[test]
+    fn test_validated_str_and_string_serde() {
+        let post = serde_json::from_str::<Post>(r#"{"slug":"a","slugs":["b-c"]}"#).expect("pass");
+        assert_eq!(post.slug.as_str(), "a");
+        assert_eq!(post.slugs[0].as_str(), "b-c");
+        assert_eq!(
+            serde_json::to_string(&post).expect("pass"),
+            r#"{"slug":"a","slugs":["b-c"]}"#
+        );
+        assert!(serde_json::from_str::<Post>(r#"{"slug":"A","slugs":[]}"#).is_err());
+    }
+}
+
+#[test]
+fn test_bounded_str_and_string() {
+    use pneutype::{
+        validators::LengthError, BoundedBytesStr, BoundedGraphemesString, BoundedStr, BoundedString,
+    };
+
+    // "é" is 1 char but 2 bytes, and "e\u{301}" (e with a combining accent) is 1 grapheme but 2 chars.
+    BoundedStr::<1, 3>::new_ref("héé").expect("pass");
+    assert_eq!(
+        BoundedStr::<1, 3>::new_ref(""),
+        Err(LengthError::TooShort {
+            min: 1,
+            actual: 0,
+            unit: "chars"
+        })
+    );
+    assert_eq!(
+        BoundedStr::<1, 3>::new_ref("héllo"),
+        Err(LengthError::TooLong {
+            max: 3,
+            actual: 5,
+            unit: "chars",
+            position: 4
+        })
+    );
+    assert_eq!(
+        BoundedBytesStr::<1, 3>::new_ref("héé"),
+        Err(LengthError::TooLong {
+            max: 3,
+            actual: 5,
+            unit: "bytes",
+            position: 3
+        })
+    );
+    assert_eq!(
+        BoundedStr::<1, 3>::new_ref("")
+            .expect_err("pass")
+            .to_string(),
+        "must be at least 1 char long, but was 0"
+    );
+    assert_eq!(
+        BoundedBytesStr::<1, 3>::new_ref("héé")
+            .expect_err("pass")
+            .to_string(),
+        "must be at most 3 bytes long, but was 5"
+    );
+    BoundedGraphemesString::<1, 2>::try_from("e\u{301}e\u{301}").expect("pass");
+    assert!(BoundedString::<1, 2>::try_from("e\u{301}e\u{301}").is_err());
+
+    // truncate_to_fit truncates on a boundary of the unit, and only fails if too short.
+    assert_eq!(
+        BoundedStr::<1, 3>::truncate_to_fit("héllo")
+            .expect("pass")
+            .as_str(),
+        "hél"
+    );
+    assert_eq!(
+        BoundedBytesStr::<1, 3>::truncate_to_fit("héllo")
+            .expect("pass")
+            .as_str(),
+        "hé"
+    );
+    assert_eq!(
+        BoundedGraphemesString::<1, 1>::truncate_to_fit("e\u{301}e\u{301}".to_string())
+            .expect("pass")
+            .as_str(),
+        "e\u{301}"
+    );
+    assert_eq!(
+        BoundedString::<1, 255>::truncate_to_fit("ok")
+            .expect("pass")
+            .as_str(),
+        "ok"
+    );
+    assert_eq!(
+        BoundedString::<2, 3>::truncate_to_fit("a"),
+        Err(LengthError::TooShort {
+            min: 2,
+            actual: 1,
+            unit: "chars"
+        })
+    );
+}
+
+/// A name is non-empty ASCII-alphanumeric, and reports where it went wrong via pneutype::ValidationError.
+#[derive(Debug, Eq, PartialEq, pneutype::PneuString)]
+#[pneu_string(borrow = "NameStr", deserialize, no_arbitrary)]
+struct Name(String);
+
+#[derive(Debug, Eq, PartialEq, pneutype::PneuStr)]
+#[repr(transparent)]
+struct NameStr(str);
+
+impl pneutype::Validate for NameStr {
+    type Data = str;
+    type Error = pneutype::ValidationError;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        if data.is_empty() {
+            return Err(pneutype::ValidationError::new(
+                "non_empty",
+                "a non-empty name",
+            ));
+        }
+        match data
+            .char_indices()
+            .find(|(_, c)| !c.is_ascii_alphanumeric())
+        {
+            Some((position, _)) => Err(pneutype::ValidationError::new(
+                "chars",
+                "ASCII alphanumeric",
+            )
+            .at_char(data, position)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn test_validation_error() {
+    let err = Name::try_from("Foo Bar").expect_err("pass");
+    assert_eq!(err.rule, "chars");
+    assert_eq!(err.span, 3..4);
+    assert_eq!(err.found, Some(' '));
+    assert_eq!(err.expected, "ASCII alphanumeric");
+    assert_eq!(
+        err.to_string(),
+        "chars: expected ASCII alphanumeric, found ' ' at byte 3"
+    );
+    assert_eq!(err.underline("Foo Bar"), "Foo Bar\n   ^");
+    // Columns are counted in chars, not bytes.
+    let err = pneutype::ValidationError::new("chars", "not '!'").at_char("éé!", 4);
+    assert_eq!(err.span, 4..5);
+    assert_eq!(err.underline("éé!"), "éé!\n  ^");
+
+    let err = NameStr::new_ref("").expect_err("pass");
+    assert_eq!(err.to_string(), "non_empty: expected a non-empty name");
+    assert_eq!(err.underline(""), "\n^");
+
+    let err = serde_json::from_str::<Name>(r#""Foo Bar""#).expect_err("pass");
+    assert_eq!(
+        err.to_string(),
+        r#"invalid value: string "Foo Bar", expected a Name (chars: expected ASCII alphanumeric, found ' ' at byte 3) at line 1 column 9"#
+    );
+
+    // The errors of the validator combinators convert into ValidationError.
+    use pneutype::validators::{And, AsciiLowercase, CharsAll, MaxLen};
+    type Rule = And<MaxLen<4>, CharsAll<AsciiLowercase>>;
+    let err = pneutype::ValidatedStr::<Rule>::new_ref("abC")
+        .map_err(pneutype::ValidationError::from)
+        .expect_err("pass");
+    assert_eq!(err.span, 2..3);
+    assert_eq!(err.underline("abC"), "abC\n  ^");
+    let err = pneutype::ValidationError::from(
+        pneutype::ValidatedStr::<Rule>::new_ref("abcde").expect_err("pass"),
+    );
+    assert_eq!(
+        err.to_string(),
+        "max_len: expected a length of at most 4 bytes (was 5)"
+    );
+    assert_eq!(err.underline("abcde"), "abcde\n    ^");
+
+    // The span of a length error is a byte offset, even when the length is measured in another unit.
+    let err = pneutype::ValidationError::from(
+        pneutype::BoundedStr::<1, 2>::new_ref("aéé").expect_err("pass"),
+    );
+    assert_eq!(err.span, 3..3);
+    assert_eq!(
+        err.to_string(),
+        "max_len: expected a length of at most 2 chars (was 3)"
+    );
+    assert_eq!(err.underline("aéé"), "aéé\n  ^");
+    // Offsets that aren't on char boundaries are clamped rather than panicking.
+    let err = pneutype::ValidationError::new("chars", "ASCII").at_char("aé", 2);
+    assert_eq!(err.span, 1..3);
+    assert_eq!(err.found, Some('é'));
+    let err = pneutype::ValidationError::new("chars", "ASCII").at_char("aé", 3);
+    assert_eq!(err.span, 3..3);
+    assert_eq!(err.found, None);
+    let err = pneutype::ValidationError::new("max_len", "at most 1 byte").with_span(2..2);
+    assert_eq!(err.underline("aé"), "aé\n ^");
+    let err = pneutype::ValidationError::new("chars", "ASCII").with_span(2..4);
+    assert_eq!(err.underline("aéb"), "aéb\n ^^");
+}
+
+#[test]
+fn test_validate_all() {
+    use pneutype::{
+        validators::{And, AsciiLowercase, CharsAll, MaxLen},
+        ValidatedStr, ValidatedString, ValidationError,
+    };
+
+    // The validator combinators report every violated rule and position.
+    type Rule = And<MaxLen<4>, CharsAll<AsciiLowercase>>;
+    let errs = ValidatedString::<Rule>::try_from_collect_errors("aBcDe")
+        .expect_err("pass")
+        .into_iter()
+        .map(ValidationError::from)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errs.iter().map(|err| err.to_string()).collect::<Vec<_>>(),
+        vec![
+            "max_len: expected a length of at most 4 bytes (was 5)",
+            "chars: expected ASCII lowercase, found 'B' at byte 1",
+            "chars: expected ASCII lowercase, found 'D' at byte 3",
+        ]
+    );
+    // Only the first is reported by the ordinary constructors.
+    assert!(matches!(
+        ValidatedStr::<Rule>::new_ref("aBcDe"),
+        Err(pneutype::validators::AndError::Left(_))
+    ));
+    ValidatedStr::<Rule>::new_ref_collect_errors("abcd").expect("pass");
+
+    // The default implementation reports the first failure.
+    assert_eq!(
+        NameStr::new_ref_collect_errors("a b c")
+            .expect_err("pass")
+            .iter()
+            .map(|err| err.span.clone())
+            .collect::<Vec<_>>(),
+        vec![1..2]
+    );
+    let n =
+        <Name as pneutype::PneuString>::try_from_collect_errors("abc".to_string()).expect("pass");
+    assert_eq!(n.as_str(), "abc");
+    assert_eq!(
+        <HandleStr as pneutype::PneuStr>::new_ref_collect_errors("Bo"),
+        Err(vec!["must be an all-lowercase string"])
+    );
+    assert_eq!(
+        Handle::try_from_collect_errors("bo"),
+        Err(vec!["must be 3 to 8 chars"])
+    );
+}
+
+/// A port number, whose conversions report a type-tagged error.
+#[derive(Debug, pneutype::PneuString)]
+#[pneu_string(borrow = "PortStr", error = "InvalidPort", no_arbitrary)]
+pub struct Port(String);
+
+#[derive(Debug, pneutype::PneuStr)]
+#[pneu_str(error = "InvalidPortStr")]
+#[repr(transparent)]
+pub struct PortStr(str);
+
+impl pneutype::Validate for PortStr {
+    type Data = str;
+    type Error = std::num::ParseIntError;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        data.parse::<u16>()?;
+        Ok(())
+    }
+}
+
+/// A code is non-empty.  Its validation error is a &'static str, so its error type has no source.
+#[derive(Debug, pneutype::PneuStr)]
+#[pneu_str(error = "InvalidCodeStr")]
+#[repr(transparent)]
+pub struct CodeStr(str);
+
+impl pneutype::Validate for CodeStr {
+    type Data = str;
+    type Error = &'static str;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        if data.is_empty() {
+            Err("must be non-empty")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[test]
+fn test_error_wrapper() {
+    fn parse_port(s: &str) -> Result<Port, Box<dyn std::error::Error>> {
+        Ok(s.parse::<Port>()?)
+    }
+
+    assert_eq!(parse_port("8080").expect("pass").as_str(), "8080");
+    assert_eq!(
+        parse_port("http").expect_err("pass").to_string(),
+        "invalid Port \"http\": invalid digit found in string"
+    );
+
+    let err = Port::try_from("70000".to_string()).expect_err("pass");
+    assert_eq!(err.input, "70000");
+    assert_eq!(err.source, "70000".parse::<u16>().unwrap_err());
+    let err: InvalidPort = Port::try_from("").expect_err("pass");
+    assert_eq!(err.input, "");
+
+    let err = <&PortStr>::try_from("-1").expect_err("pass");
+    assert_eq!(
+        err.to_string(),
+        "invalid PortStr \"-1\": invalid digit found in string"
+    );
+    let _: &dyn std::error::Error = &err;
+    // The validation error is the source, where it implements std::error::Error.
+    use std::error::Error;
+    assert_eq!(
+        err.source().expect("pass").to_string(),
+        "invalid digit found in string"
+    );
+    let err = <&CodeStr>::try_from("").expect_err("pass");
+    assert_eq!(err.to_string(), "invalid CodeStr \"\": must be non-empty");
+    assert!(err.source().is_none());
+    // new_ref still returns the validation error itself.
+    assert!(PortStr::new_ref("-1").is_err());
+}
+
+/// An identifier has the same constraint as a name, and its rejected conversions are rendered as diagnostics.
+#[derive(Debug, pneutype::PneuStr)]
+#[pneu_str(error = "InvalidIdentStr", diagnostic)]
+#[repr(transparent)]
+pub struct IdentStr(str);
+
+impl pneutype::Validate for IdentStr {
+    type Data = str;
+    type Error = pneutype::ValidationError;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        <NameStr as pneutype::Validate>::validate(data)
+    }
+}
+
+#[test]
+fn test_miette_diagnostic() {
+    use pneutype::miette::{Diagnostic, LabeledSpan};
+
+    let err = <&IdentStr>::try_from("foo-bar").expect_err("pass");
+    assert_eq!(err.code().expect("pass").to_string(), "chars");
+    assert_eq!(
+        err.help().expect("pass").to_string(),
+        "expected ASCII alphanumeric"
+    );
+    assert_eq!(
+        err.labels().expect("pass").collect::<Vec<_>>(),
+        vec![LabeledSpan::new_primary_with_span(
+            Some("found '-'".to_string()),
+            3..4
+        )]
+    );
+    let source_code = err.source_code().expect("pass");
+    let span = source_code.read_span(&(3..4).into(), 0, 0).expect("pass");
+    assert_eq!(span.data(), b"-");
+
+    // The labels are byte spans, so they line up with non-ASCII input too.
+    let err = <&IdentStr>::try_from("abé").expect_err("pass");
+    assert_eq!(
+        err.labels().expect("pass").collect::<Vec<_>>(),
+        vec![LabeledSpan::new_primary_with_span(
+            Some("found 'é'".to_string()),
+            2..4
+        )]
+    );
+    let source_code = err.source_code().expect("pass");
+    let span = source_code.read_span(&(2..4).into(), 0, 0).expect("pass");
+    assert_eq!(span.data(), "é".as_bytes());
+    let err = pneutype::ValidationError::from(
+        pneutype::BoundedStr::<1, 2>::new_ref("aéé").expect_err("pass"),
+    );
+    assert_eq!(
+        err.labels().expect("pass").collect::<Vec<_>>(),
+        vec![LabeledSpan::new_primary_with_span(
+            Some("here".to_string()),
+            3..3
+        )]
+    );
+
+    // Without the input, the ValidationError itself still labels the span.
+    let err = IdentStr::new_ref("").expect_err("pass");
+    assert!(err.source_code().is_none());
+    assert_eq!(
+        err.labels().expect("pass").collect::<Vec<_>>(),
+        vec![LabeledSpan::new_primary_with_span(
+            Some("here".to_string()),
+            0..0
+        )]
+    );
+}
+
+/// A ticker symbol is 1 to 5 ASCII uppercase letters, which is described by its constraint.
+#[derive(Debug, pneutype::PneuString)]
+#[pneu_string(borrow = "TickerStr", deserialize, expecting)]
+pub struct Ticker(String);
+
+#[derive(Debug, pneutype::PneuStr)]
+#[pneu_str(
+    deserialize,
+    expecting,
+    export_ts = "Ticker",
+    constraint = r#"pneutype::Constraint::AllOf(vec![
+        pneutype::Constraint::Length { unit: "chars", min: Some(1), max: Some(5) },
+        pneutype::Constraint::Chars { description: "ASCII uppercase".into(), regex_class: Some("A-Z".into()) },
+    ])"#
+)]
+#[repr(transparent)]
+pub struct TickerStr(str);
+
+impl pneutype::Validate for TickerStr {
+    type Data = str;
+    type Error = &'static str;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        if (1..=5).contains(&data.len()) && data.chars().all(|c| c.is_ascii_uppercase()) {
+            Ok(())
+        } else {
+            Err("TickerStr must be 1 to 5 ASCII uppercase letters")
+        }
+    }
+}
+
+#[test]
+fn test_describe() {
+    use pneutype::{
+        validators::{And, AsciiLowercase, CharsAll, MaxLen, Not, Or},
+        BoundedString, Constraint, Describe, ValidatedStr,
+    };
+
+    // Declared constraints.
+    assert_eq!(TickerStr::TYPE_NAME, "TickerStr");
+    assert_eq!(Ticker::TYPE_NAME, "Ticker");
+    assert_eq!(Ticker::constraint(), TickerStr::constraint());
+    assert_eq!(
+        Ticker::description(),
+        "1 to 5 chars and only ASCII uppercase chars"
+    );
+    assert_eq!(
+        Ticker::constraint().length_bounds("chars"),
+        (Some(1), Some(5))
+    );
+    assert_eq!(Ticker::constraint().length_bounds("bytes"), (None, None));
+    let err = serde_json::from_str::<Ticker>(r#""msft""#).expect_err("pass");
+    assert_eq!(
+        err.to_string(),
+        r#"invalid value: string "msft", expected a Ticker (1 to 5 chars and only ASCII uppercase chars) at line 1 column 6"#
+    );
+    let err = serde_json::from_str::<&TickerStr>("[]").expect_err("pass");
+    assert_eq!(
+        err.to_string(),
+        "invalid type: sequence, expected a borrowed TickerStr (1 to 5 chars and only ASCII uppercase chars) at line 1 column 0"
+    );
+
+    // Constraints given by other attributes.
+    assert_eq!(
+        LowercaseStr::constraint(),
+        Constraint::Custom("all-lowercase ASCII".into())
+    );
+    assert_eq!(Lowercase::description(), "all-lowercase ASCII");
+    assert_eq!(
+        EnvironmentStr::description(),
+        r#"one of "dev", "staging", "prod", "us-east-1""#
+    );
+    assert_eq!(RoleStr::description(), "a valid RoleStr");
+
+    // Validators describe themselves structurally.
+    assert_eq!(BoundedString::<1, 8>::description(), "1 to 8 chars");
+    type Rule = And<MaxLen<8>, And<CharsAll<AsciiLowercase>, Not<Or<MaxLen<0>, MaxLen<1>>>>>;
+    assert_eq!(
+        ValidatedStr::<Rule>::description(),
+        "at most 8 bytes and only ASCII lowercase chars and not (at most 0 bytes or at most 1 bytes)"
+    );
+    assert_eq!(
+        ValidatedStr::<Rule>::constraint().length_bounds("bytes"),
+        (None, Some(8))
+    );
+}
+
+/// A hostname, whose JSON schema has a format.
+#[derive(Debug, pneutype::PneuStr)]
+#[pneu_str(
+    export_ts,
+    constraint = r#"pneutype::Constraint::Pattern("[a-z0-9.-]+".into())"#,
+    schema_format = "hostname",
+    schema_description = "The DNS name of a host"
+)]
+#[repr(transparent)]
+pub struct DnsNameStr(str);
+
+impl pneutype::Validate for DnsNameStr {
+    type Data = str;
+    type Error = &'static str;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        if !data.is_empty()
+            && data
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '.' || c == '-')
+        {
+            Ok(())
+        } else {
+            Err("DnsNameStr must be a non-empty lowercase DNS name")
+        }
+    }
+}
+
+/// A token, whose schemas and generators are implemented manually rather than derived.
+#[derive(Debug, pneutype::PneuString)]
+#[pneu_string(borrow = "TokenStr", no_schema, no_arbitrary)]
+pub struct Token(String);
+
+#[derive(Debug, pneutype::PneuStr)]
+#[pneu_str(no_schema)]
+#[repr(transparent)]
+pub struct TokenStr(str);
+
+impl pneutype::Validate for TokenStr {
+    type Data = str;
+    type Error = &'static str;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        if data.is_empty() {
+            Err("TokenStr must be non-empty")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl pneutype::schemars::JsonSchema for Token {
+    fn schema_name() -> Cow<'static, str> {
+        "Token".into()
+    }
+    fn json_schema(
+        _generator: &mut pneutype::schemars::SchemaGenerator,
+    ) -> pneutype::schemars::Schema {
+        pneutype::schemars::json_schema!({ "type": "string", "writeOnly": true })
+    }
+}
+
+impl pneutype::proptest::arbitrary::Arbitrary for Token {
+    type Parameters = ();
+    type Strategy = pneutype::proptest::strategy::BoxedStrategy<Self>;
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        use pneutype::proptest::strategy::Strategy;
+        "[a-z]{1,8}"
+            .prop_map(|s| Token::try_from(s).expect("pass"))
+            .boxed()
+    }
+}
+
+#[test]
+fn test_json_schema() {
+    use pneutype::{
+        schemars::schema_for,
+        validators::{And, AsciiDigit, CharIs, CharOr, CharsAll, MaxLen, NonWhitespace, Not, Or},
+        BoundedString, ValidatedString,
+    };
+    use serde_json::json;
+
+    fn schema_value(schema: pneutype::schemars::Schema) -> serde_json::Value {
+        let mut value = serde_json::to_value(schema).expect("pass");
+        value.as_object_mut().expect("pass").remove("$schema");
+        value
+    }
+
+    // Both halves of a pair have the same schema.
+    let ticker_schema = json!({
+        "title": "TickerStr",
+        "type": "string",
+        "minLength": 1,
+        "maxLength": 5,
+        "pattern": "^[A-Z]*$",
+        "description": "1 to 5 chars and only ASCII uppercase chars",
+    });
+    assert_eq!(schema_value(schema_for!(Ticker)), ticker_schema);
+    assert_eq!(schema_value(schema_for!(TickerStr)), ticker_schema);
+    assert_eq!(
+        schema_value(schema_for!(Environment)),
+        json!({
+            "title": "EnvironmentStr",
+            "type": "string",
+            "enum": ["dev", "staging", "prod", "us-east-1"],
+            "description": r#"one of "dev", "staging", "prod", "us-east-1""#,
+        })
+    );
+    assert_eq!(
+        schema_value(schema_for!(DnsNameStr)),
+        json!({
+            "title": "DnsNameStr",
+            "type": "string",
+            "pattern": "^(?:[a-z0-9.-]+)$",
+            "format": "hostname",
+            "description": "The DNS name of a host",
+        })
+    );
+    // An undeclared constraint is only described.
+    assert_eq!(
+        schema_value(schema_for!(Lowercase)),
+        json!({
+            "title": "LowercaseStr",
+            "type": "string",
+            "description": "all-lowercase ASCII",
+        })
+    );
+
+    // The validators are expressed as far as possible.
+    assert_eq!(
+        schema_value(schema_for!(BoundedString<1, 8>)),
+        json!({
+            "title": "ValidatedStr",
+            "type": "string",
+            "minLength": 1,
+            "maxLength": 8,
+            "description": "1 to 8 chars",
+        })
+    );
+    type Rule = pneutype::validators::And<
+        MaxLen<8>,
+        pneutype::validators::And<
+            CharsAll<CharOr<AsciiDigit, CharIs<'-'>>>,
+            Not<Or<MaxLen<0>, CharsAll<CharIs<'-'>>>>,
+        >,
+    >;
+    assert_eq!(
+        schema_value(schema_for!(ValidatedString<Rule>)),
+        json!({
+            "title": "ValidatedStr",
+            "type": "string",
+            "maxLength": 8,
+            "pattern": "^[0-9\\-]*$",
+            "not": { "anyOf": [{ "maxLength": 0 }, { "pattern": "^[\\-]*$" }] },
+            "description": "at most 8 bytes and only ASCII digit or '-' chars and not (at most 0 bytes or only '-' chars)",
+        })
+    );
+
+    // A negation is only expressed if the negated keywords are exact, since otherwise it would reject valid
+    // strings, e.g. "ééé" is longer than 3 bytes but not than 3 chars.
+    assert!(pneutype::ValidatedStr::<Not<MaxLen<3>>>::new_ref("ééé").is_ok());
+    assert_eq!(
+        schema_value(schema_for!(ValidatedString<Not<MaxLen<3>>>)),
+        json!({
+            "title": "ValidatedStr",
+            "type": "string",
+            "description": "not at most 3 bytes",
+        })
+    );
+    assert_eq!(
+        schema_value(schema_for!(
+            ValidatedString<Not<And<CharsAll<AsciiDigit>, CharsAll<NonWhitespace>>>>
+        ))
+        .get("not"),
+        None
+    );
+    assert_eq!(
+        schema_value(schema_for!(
+            ValidatedString<Not<Or<MaxLen<0>, CharsAll<AsciiDigit>>>>
+        ))["not"],
+        json!({ "anyOf": [{ "maxLength": 0 }, { "pattern": "^[0-9]*$" }] })
+    );
+
+    // With no_schema, the manual impl is used.
+    assert_eq!(
+        schema_value(schema_for!(Token)),
+        json!({ "title": "Token", "type": "string", "writeOnly": true })
+    );
+
+    // Referenced from a containing schema.
+    #[derive(schemars::JsonSchema)]
+    #[allow(dead_code)]
+    struct Listing {
+        ticker: Ticker,
+    }
+    let listing = schema_value(schema_for!(Listing));
+    assert_eq!(
+        listing["properties"]["ticker"],
+        json!({ "$ref": "#/$defs/TickerStr" })
+    );
+    assert_eq!(listing["$defs"]["TickerStr"]["maxLength"], json!(5));
+}
+
+#[test]
+fn test_openapi_schema() {
+    use pneutype::{
+        utoipa::{PartialSchema, ToSchema},
+        validators::{And, AsciiLowercase, CharIs, CharOr, CharsAll, MaxLen},
+        BoundedString, ValidatedStr,
+    };
+    use serde_json::json;
+
+    fn schema_value<T: PartialSchema + ?Sized>() -> serde_json::Value {
+        serde_json::to_value(T::schema()).expect("pass")
+    }
+
+    // Both halves of a pair have the same schema.
+    assert_eq!(
+        schema_value::<Ticker>(),
+        json!({
+            "type": "string",
+            "minLength": 1,
+            "maxLength": 5,
+            "pattern": "^[A-Z]*$",
+            "description": "1 to 5 chars and only ASCII uppercase chars",
+        })
+    );
+    assert_eq!(schema_value::<TickerStr>(), schema_value::<Ticker>());
+    assert_eq!(<Ticker as ToSchema>::name(), "TickerStr");
+    assert_eq!(
+        schema_value::<Environment>()["enum"],
+        json!(["dev", "staging", "prod", "us-east-1"])
+    );
+    assert_eq!(
+        schema_value::<DnsNameStr>(),
+        json!({
+            "type": "string",
+            "pattern": "^(?:[a-z0-9.-]+)$",
+            "format": "hostname",
+            "description": "The DNS name of a host",
+        })
+    );
+    assert_eq!(
+        schema_value::<Lowercase>(),
+        json!({ "type": "string", "description": "all-lowercase ASCII" })
+    );
+
+    // Different instantiations of a generic pneutype have different names.
+    assert_eq!(
+        <BoundedString<1, 8> as ToSchema>::name(),
+        "ValidatedStr_Bounded_chars_1_8"
+    );
+    assert_eq!(
+        <BoundedString<1, 9> as ToSchema>::name(),
+        "ValidatedStr_Bounded_chars_1_9"
+    );
+    // Multiple patterns are combined into one.
+    type Rule =
+        And<MaxLen<8>, And<CharsAll<AsciiLowercase>, CharsAll<pneutype::validators::Ascii>>>;
+    assert_eq!(
+        schema_value::<ValidatedStr<Rule>>(),
+        json!({
+            "type": "string",
+            "maxLength": 8,
+            "pattern": "(?=^[a-z]*$)(?=^[\\x00-\\x7F]*$)",
+            "description": "at most 8 bytes and only ASCII lowercase chars and only ASCII chars",
+        })
+    );
+    assert_eq!(
+        <ValidatedStr<Rule> as ToSchema>::name(),
+        "ValidatedStr_And_MaxLen_8_And_CharsAll_AsciiLowercase_CharsAll_Ascii"
+    );
+    type Slug = CharsAll<CharOr<AsciiLowercase, CharIs<'-'>>>;
+    assert_eq!(
+        <ValidatedStr<Slug> as ToSchema>::name(),
+        "ValidatedStr_CharsAll_CharOr_AsciiLowercase_CharIs_2D"
+    );
+
+    // Referenced from a containing schema.
+    #[derive(utoipa::ToSchema)]
+    #[allow(dead_code)]
+    struct Listing {
+        ticker: Ticker,
+    }
+    assert_eq!(
+        schema_value::<Listing>()["properties"]["ticker"],
+        json!({ "$ref": "#/components/schemas/TickerStr" })
+    );
+    let mut schemas = Vec::new();
+    <Listing as ToSchema>::schemas(&mut schemas);
+    assert_eq!(
+        schemas
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>(),
+        vec!["TickerStr".to_string()]
+    );
+}
+
+/// A port in TypeScript, which is only described in prose.
+#[derive(Debug, pneutype::PneuStr)]
+#[pneu_str(export_ts, expecting = "a port number")]
+#[repr(transparent)]
+pub struct PortTsStr(str);
+
+impl pneutype::Validate for PortTsStr {
+    type Data = str;
+    type Error = std::num::ParseIntError;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        data.parse::<u16>()?;
+        Ok(())
+    }
+}
+
+/// A role in TypeScript, which is described by its values.
+#[derive(Debug, pneutype::PneuStr)]
+#[pneu_str(one_of("viewer", "editor"), export_ts = "UiRole")]
+#[repr(transparent)]
+pub struct UiRoleStr(str);
+
+#[test]
+fn test_export_ts() {
+    use pneutype::ExportTs;
+
+    assert_eq!(
+        TickerStr::ts_definition(),
+        r#"// Generated by pneutype from TickerStr.  Do not edit.
+
+/** 1 to 5 chars and only ASCII uppercase chars */
+export type Ticker = string & { __brand: "Ticker" };
+
+/** Returns true iff `s` is a valid Ticker. */
+export function isTicker(s: string): s is Ticker {
+  return [...s].length >= 1 && [...s].length <= 5 && /^[A-Z]*$/u.test(s);
+}
+"#
+    );
+    assert!(DnsNameStr::ts_definition()
+        .contains("export type DnsNameStr = string & { __brand: \"DnsNameStr\" };"));
+    assert!(DnsNameStr::ts_definition().contains("return /^(?:[a-z0-9.-]+)$/u.test(s);"));
+    assert!(UiRoleStr::ts_definition().contains(r#"return ["viewer", "editor"].includes(s);"#));
+    // Only the type is exported if the constraint isn't declarative.
+    assert!(!PortTsStr::ts_definition().contains("function"));
+
+    let dir = std::env::temp_dir().join(format!("pneutype-export-ts-{}", std::process::id()));
+    let path = TickerStr::export_ts_to(&dir).expect("pass");
+    assert_eq!(path, dir.join("Ticker.ts"));
+    assert_eq!(
+        std::fs::read_to_string(&path).expect("pass"),
+        TickerStr::ts_definition()
+    );
+    std::fs::remove_dir_all(&dir).expect("pass");
+
+    // The type guards agree with the validation (as also checked by the generated tests).
+    TickerStr::check_ts_guard();
+    UiRoleStr::check_ts_guard();
+}
+
+/// A currency code is 3 ASCII uppercase letters, but its constraint (mistakenly) allows 1 to 5, so its type guard
+/// accepts values that aren't valid.
+#[derive(Debug, pneutype::PneuStr)]
+#[pneu_str(constraint = r#"pneutype::Constraint::AllOf(vec![
+    pneutype::Constraint::Length { unit: "chars", min: Some(1), max: Some(5) },
+    pneutype::Constraint::Chars { description: "ASCII uppercase".into(), regex_class: Some("A-Z".into()) },
+])"#)]
+#[repr(transparent)]
+pub struct CurrencyStr(str);
+
+impl pneutype::Validate for CurrencyStr {
+    type Data = str;
+    type Error = &'static str;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        if data.len() == 3 && data.chars().all(|c| c.is_ascii_uppercase()) {
+            Ok(())
+        } else {
+            Err("CurrencyStr must be 3 ASCII uppercase letters")
+        }
+    }
+}
+
+// This is implemented manually, since the derived impl's generated test would fail.
+impl pneutype::ExportTs for CurrencyStr {
+    const TS_NAME: &'static str = "Currency";
+}
+
+#[test]
+#[should_panic(
+    expected = r#""A" is accepted by the TypeScript guard isCurrency but isn't a valid CurrencyStr: CurrencyStr must be 3 ASCII uppercase letters"#
+)]
+fn test_export_ts_check_ts_guard() {
+    use pneutype::ExportTs;
+    CurrencyStr::check_ts_guard();
+}
+
+/// A PIN is an even number (at least 4) of ASCII digits, which isn't described declaratively, so values are
+/// generated from the seeds.
+#[derive(Debug, pneutype::PneuString)]
+#[pneu_string(
+    borrow = "PinStr",
+    proptest_seed = "pin_proptest_seed",
+    arbitrary_seed = "pin_arbitrary_seed"
+)]
+pub struct Pin(String);
+
+#[derive(Debug, pneutype::PneuStr)]
+#[pneu_str(expecting = "an even number of ASCII digits, at least 4")]
+#[repr(transparent)]
+pub struct PinStr(str);
+
+impl pneutype::Validate for PinStr {
+    type Data = str;
+    type Error = &'static str;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        if data.len() >= 4 && data.len() % 2 == 0 && data.chars().all(|c| c.is_ascii_digit()) {
+            Ok(())
+        } else {
+            Err("PinStr must be an even number of ASCII digits, at least 4")
+        }
+    }
+}
+
+fn pin_proptest_seed() -> pneutype::proptest::strategy::BoxedStrategy<String> {
+    use pneutype::proptest::strategy::Strategy;
+    pneutype::proptest::string::string_regex("[0-9]{3,8}")
+        .expect("pass")
+        .boxed()
+}
+
+fn pin_arbitrary_seed(
+    u: &mut pneutype::arbitrary::Unstructured<'_>,
+) -> pneutype::arbitrary::Result<String> {
+    let len = u.int_in_range(3..=8)?;
+    (0..len)
+        .map(|_| Ok(char::from(b'0' + u.int_in_range(0..=9)?)))
+        .collect()
+}
+
+#[test]
+fn test_proptest_arbitrary() {
+    use pneutype::{
+        proptest::{
+            arbitrary::any,
+            strategy::{Strategy, ValueTree},
+            test_runner::TestRunner,
+        },
+        Validate,
+    };
+
+    /// Checks that generated values, and every step of shrinking them, are valid.
+    fn check<P>()
+    where
+        P: pneutype::proptest::arbitrary::Arbitrary + std::ops::Deref,
+        P::Target: AsRef<str> + Validate<Data = str>,
+    {
+        let mut runner = TestRunner::deterministic();
+        let strategy = any::<P>();
+        for _ in 0..32 {
+            let mut value_tree = strategy.new_tree(&mut runner).expect("pass");
+            loop {
+                assert!(P::Target::validate(value_tree.current().as_ref()).is_ok());
+                if !value_tree.simplify() {
+                    break;
+                }
+            }
+        }
+    }
+
+    // Declarative constraints are generated from directly.
+    check::<Ticker>();
+    // With no_arbitrary, the manual impl is used.
+    check::<Token>();
+    check::<Environment>();
+    // Otherwise the seed is used.
+    check::<Pin>();
+
+    // Patterns are generated from as well.
+    let mut runner = TestRunner::deterministic();
+    let strategy = pneutype::generate::proptest_strategy::<DnsNameStr>(None);
+    for _ in 0..32 {
+        let s = strategy.new_tree(&mut runner).expect("pass").current();
+        assert!(DnsNameStr::validate(&s).is_ok());
+    }
+
+    // Every value generated from fuzzer input is valid, and invalid candidates are skipped.
+    let mut generated_count = 0;
+    for i in 0..64u8 {
+        let data = (0..64u8)
+            .map(|j| i.wrapping_mul(31).wrapping_add(j.wrapping_mul(17)))
+            .collect::<Vec<_>>();
+        let mut u = pneutype::arbitrary::Unstructured::new(&data);
+        let ticker = <Ticker as pneutype::arbitrary::Arbitrary>::arbitrary(&mut u).expect("pass");
+        assert!(TickerStr::validate(&ticker).is_ok());
+        let environment =
+            <Environment as pneutype::arbitrary::Arbitrary>::arbitrary(&mut u).expect("pass");
+        assert!(EnvironmentStr::validate(&environment).is_ok());
+        match <Pin as pneutype::arbitrary::Arbitrary>::arbitrary(&mut u) {
+            Ok(pin) => {
+                assert!(PinStr::validate(&pin).is_ok());
+                generated_count += 1;
+            }
+            Err(error) => assert!(matches!(error, pneutype::arbitrary::Error::IncorrectFormat)),
+        }
+    }
+    assert!(generated_count > 0);
+}
+
+#[test]
+fn test_constraint_is_custom() {
+    use pneutype::Describe;
+
+    // Without a `constraint` or `one_of` attribute, the constraint is only known to be prose.
+    assert_eq!(
+        [
+            PinStr::CONSTRAINT_IS_CUSTOM,
+            Pin::CONSTRAINT_IS_CUSTOM,
+            DnsNameStr::CONSTRAINT_IS_CUSTOM,
+            EnvironmentStr::CONSTRAINT_IS_CUSTOM,
+            pneutype::BoundedString::<1, 8>::CONSTRAINT_IS_CUSTOM,
+        ],
+        [true, true, false, false, false]
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "values of PinStr can't be generated from its constraint (an even number of ASCII digits, at least 4), so the PneuString derive needs `proptest_seed`"
+)]
+fn test_proptest_requires_seed() {
+    let _ = pneutype::generate::proptest_strategy::<PinStr>(None);
+}
+
+#[test]
+#[should_panic(
+    expected = "values of DnsNameStr can't be generated from its constraint (matching /[a-z0-9.-]+/), so the PneuString derive needs `arbitrary_seed`"
+)]
+fn test_arbitrary_requires_seed_for_pattern() {
+    let mut u = pneutype::arbitrary::Unstructured::new(&[0; 64]);
+    let _ = pneutype::generate::arbitrary_string::<DnsNameStr>(&mut u, None);
+}
+
+/// A label is a non-empty string, whose comparisons and hashing are those of str.
+#[derive(Debug, pneutype::PneuString)]
+#[pneu_string(borrow = "LabelStr", str_cmp, no_arbitrary)]
+pub struct Label(String);
+
+#[derive(Debug, pneutype::PneuStr)]
+#[pneu_str(str_cmp)]
+#[repr(transparent)]
+pub struct LabelStr(str);
+
+impl pneutype::Validate for LabelStr {
+    type Data = str;
+    type Error = &'static str;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        if data.is_empty() {
+            Err("LabelStr must be non-empty")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A string of digits parsing as a T, whose comparisons and hashing are those of str regardless of T.
+#[derive(Debug, pneutype::PneuString)]
+#[pneu_string(borrow = "DigitsStr", str_cmp, string_field = "1")]
+pub struct Digits<T: 'static>(std::marker::PhantomData<T>, String);
+
+#[derive(Debug, pneutype::PneuStr)]
+#[pneu_str(str_cmp, str_field = "1")]
+#[repr(transparent)]
+pub struct DigitsStr<T: 'static>(std::marker::PhantomData<T>, str);
+
+impl<T> pneutype::Validate for DigitsStr<T> {
+    type Data = str;
+    type Error = &'static str;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        if !data.is_empty() && data.chars().all(|c| c.is_ascii_digit()) {
+            Ok(())
+        } else {
+            Err("DigitsStr must be a non-empty string of ASCII digits")
+        }
+    }
+}
+
+#[test]
+fn test_str_cmp() {
+    let label = Label::try_from("abc").expect("pass");
+    let label_str = LabelStr::new_ref("abc").expect("pass");
+    let s = String::from("abc");
+    let cow = Cow::Borrowed("abc");
+
+    // Equality in both directions, between all the str-like types.
+    assert!(label == *label_str);
+    assert!(*label_str == label);
+    assert!(label == label_str);
+    assert!(label_str == label);
+    assert!(label == *"abc");
+    assert!(*"abc" == label);
+    assert!(label == "abc");
+    assert!("abc" == label);
+    assert!(label == s);
+    assert!(s == label);
+    assert!(label == cow);
+    assert!(cow == label);
+    assert!(*label_str == *"abc");
+    assert!(*"abc" == *label_str);
+    assert!(*label_str == "abc");
+    assert!("abc" == *label_str);
+    assert!(label_str == *"abc");
+    assert!(*"abc" == label_str);
+    assert!(label_str == "abc");
+    assert!("abc" == label_str);
+    assert!(*label_str == s);
+    assert!(s == *label_str);
+    assert!(label_str == s);
+    assert!(s == label_str);
+    assert!(*label_str == cow);
+    assert!(cow == *label_str);
+    assert!(label_str == cow);
+    assert!(cow == label_str);
+    assert!(label != "abd");
+    assert!("abd" != label_str);
+
+    // Ordering is that of str.
+    assert!(label < "abd");
+    assert!("abb" < label);
+    assert!(label_str < "abd");
+    assert!(Cow::Borrowed("abb") < label_str);
+    assert!(Label::try_from("b").expect("pass") > label);
+    assert!(LabelStr::new_ref("B").expect("pass") < label_str);
+
+    // Maps and sets can be looked up by str.
+    let hash_map = HashMap::from([(Label::try_from("abc").expect("pass"), 1)]);
+    assert_eq!(hash_map.get("abc"), Some(&1));
+    assert_eq!(hash_map.get(label_str), Some(&1));
+    let btree_map = BTreeMap::from([(label, 1), (Label::try_from("xyz").expect("pass"), 2)]);
+    assert_eq!(btree_map.get("xyz"), Some(&2));
+    assert_eq!(btree_map.get(label_str), Some(&1));
+    let hash_set = HashSet::from([label_str]);
+    assert!(hash_set.contains(LabelStr::new_ref("abc").expect("pass")));
+
+    // Generic pneutypes don't need any bounds on their parameters.
+    struct NotHash;
+    let digits = Digits::<NotHash>::try_from("0123").expect("pass");
+    assert!(digits == "0123");
+    assert!("0123" == digits);
+    assert!(digits < *DigitsStr::<NotHash>::new_ref("1").expect("pass"));
+    let hash_set = HashSet::from([digits]);
+    assert!(hash_set.contains("0123"));
+}
+
+pneutype::pneutype_conformance!(
+    label_conformance: Label,
+    valid = ["abc", "ABC", "a b"],
+    invalid = [""],
+    std_impls,
+    hash,
+);
+
+pneutype::pneutype_conformance!(
+    digits_conformance: Digits<()>,
+    valid = ["0", "0123", "123"],
+    invalid = ["", "12a"],
+    std_impls,
+    hash,
+);
+
+/// An API key is an opaque secret, which isn't displayable and doesn't expose the str API, and whose unchecked
+/// constructors are only visible in this module.
+#[derive(pneutype::PneuString)]
+#[pneu_string(
+    borrow = "ApiKeyStr",
+    no_deref,
+    no_display,
+    no_from_str,
+    vis(
+        new_unchecked = "",
+        new_unchecked_const = "",
+        into_string = "pub(crate)"
+    ),
+    no_arbitrary
+)]
+pub struct ApiKey(String);
+
+#[derive(pneutype::PneuStr)]
+#[pneu_str(
+    no_deref,
+    no_display,
+    vis(new_ref_unchecked = "", new_ref_unchecked_const = "")
+)]
+#[repr(transparent)]
+pub struct ApiKeyStr(str);
+
+impl pneutype::Validate for ApiKeyStr {
+    type Data = str;
+    type Error = &'static str;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        if data.starts_with("key-") {
+            Ok(())
+        } else {
+            Err("ApiKeyStr must start with \"key-\"")
+        }
+    }
+}
+
+impl std::fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ApiKey(<redacted>)")
+    }
+}
+
+impl std::fmt::Debug for ApiKeyStr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ApiKeyStr(<redacted>)")
+    }
+}
+
+/// A custom FromStr, which trims surrounding whitespace.
+impl FromStr for ApiKey {
+    type Err = &'static str;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s.trim())
+    }
+}
+
+#[test]
+fn test_impl_opt_outs() {
+    let api_key = ApiKey::from_str(" key-123\n").expect("pass");
+    assert_eq!(api_key.as_str(), "key-123");
+    assert_eq!(format!("{:?}", api_key), "ApiKey(<redacted>)");
+    let api_key_str = ApiKeyStr::new_ref("key-123").expect("pass");
+    assert_eq!(api_key_str.as_str(), "key-123");
+    assert_eq!(api_key.as_pneu_str().as_str(), "key-123");
+    assert_eq!(
+        <ApiKey as AsRef<ApiKeyStr>>::as_ref(&api_key).as_str(),
+        "key-123"
+    );
+    assert_eq!(api_key_str.to_owned().into_string(), "key-123");
+    assert!(ApiKeyStr::new_ref("123").is_err());
+    ApiKey::from_str("123").expect_err("pass");
+    // The opt-outs don't affect the pneutype traits.
+    fn pneu_string_len<T: pneutype::PneuString>(t: &T) -> usize {
+        pneutype::AsStr::as_str(t.as_pneu_str()).len()
+    }
+    assert_eq!(pneu_string_len(&api_key), 7);
+}
+
+// Without std_impls, since ApiKey has none of Deref, Display or the generated FromStr.
+pneutype::pneutype_conformance!(
+    api_key_conformance: ApiKey,
+    valid = ["key-", "key-123"],
+    invalid = ["", "123"],
+);
+
+#[test]
+fn test_pneu_str_with_generics() {
+    type I32Str = ValueStr<i32>;
+
+    I32Str::new_ref("").expect_err("pass");
+    I32Str::new_ref("abc").expect_err("pass");
+    I32Str::new_ref("12.34").expect_err("pass");
+
+    let i = I32Str::new_ref("123").expect("pass");
+    assert_eq!(i.as_str(), "123");
+    assert_eq!(i.to_value(), 123i32);
+
+    type BoolStr = ValueStr<bool>;
+
+    BoolStr::new_ref("").expect_err("pass");
+    BoolStr::new_ref("blah").expect_err("pass");
+    BoolStr::new_ref("1").expect_err("pass");
+
+    let b = BoolStr::new_ref("true").expect("pass");
+    assert_eq!(b.as_str(), "true");
+    assert_eq!(b.to_value(), true);
+}
+
+#[test]
+fn test_pneu_string_with_generics() {
+    type I32String = ValueString<i32>;
+    type I32Str = ValueStr<i32>;
+
+    I32String::try_from("").expect_err("pass");
+    I32String::try_from("abc").expect_err("pass");
+    I32String::try_from("12.34").expect_err("pass");
+
+    let mut i = I32String::try_from("123").expect("pass");
+    assert_eq!(i.as_pneu_str(), I32Str::new_ref("123").expect("pass"));
+    assert_eq!(i.as_str(), "123");
+    assert_eq!(i.to_value(), 123i32);
+    i.set_value(&456i32);
+    assert_eq!(i.as_pneu_str(), I32Str::new_ref("456").expect("pass"));
+    assert_eq!(i.as_str(), "456");
+    assert_eq!(i.to_value(), 456i32);
+
+    type BoolString = ValueString<bool>;
+    type BoolStr = ValueStr<bool>;
+
+    BoolString::try_from("").expect_err("pass");
+    BoolString::try_from("blah").expect_err("pass");
+    BoolString::try_from("1").expect_err("pass");
+
+    let mut i = BoolString::try_from("true").expect("pass");
+    assert_eq!(i.as_pneu_str(), BoolStr::new_ref("true").expect("pass"));
+    assert_eq!(i.as_str(), "true");
+    assert_eq!(i.to_value(), true);
+    i.set_value(&false);
+    assert_eq!(i.as_pneu_str(), BoolStr::new_ref("false").expect("pass"));
+    assert_eq!(i.as_str(), "false");
+    assert_eq!(i.to_value(), false);
+}
+
+#[test]
+fn test_pneu_string_with_generics_serde() {
+    type I32String = ValueString<i32>;
+    type I32Str = ValueStr<i32>;
+
+    {
+        let i = I32String::try_from("123").expect("pass");
+        println!("i: {:?}", i);
+        assert_eq!(i.as_pneu_str(), I32Str::new_ref("123").expect("pass"));
+        let i_json = serde_json::to_string(&i).expect("pass");
+        println!("i_json: {}", i_json);
+        assert_eq!(i_json, "\"123\"");
+        let i_deserialized: I32String = serde_json::from_str(&i_json).expect("pass");
+        println!("i_deserialized: {:?}", i_deserialized);
+        assert_eq!(i_deserialized, i);
+    }
+
+    {
+        let i = I32Str::new_ref("123").expect("pass");
+        println!("i: {:?}", i);
+        let i_json = serde_json::to_string(&i).expect("pass");
+        println!("i_json: {}", i_json);
+        assert_eq!(i_json, "\"123\"");
+        let i_deserialized: &I32Str = serde_json::from_str(&i_json).expect("pass");
+        println!("i_deserialized: {:?}", i_deserialized);
+        assert_eq!(i_deserialized, i);
+    }
+}
+
+#[test]
+fn test_pneu_str_trait() {
+    let x = <LowercaseStr as pneutype::PneuStr>::new_ref("abcxyz").expect("pass");
+    assert_eq!(<LowercaseStr as pneutype::AsStr>::as_str(x), "abcxyz");
+
+    let y = unsafe { <LowercaseStr as pneutype::NewRefUnchecked>::new_ref_unchecked("abcpqr") };
+    assert_eq!(<LowercaseStr as pneutype::AsStr>::as_str(y), "abcpqr");
+}
+
+fn test_pneu_str_trait_case<T>(valid_str: &str, invalid_str: &str)
+where
+    T: std::fmt::Debug + pneutype::PneuStr + ?Sized,
+{
+    let x = T::new_ref(valid_str).expect("pass");
+    assert_eq!(x.as_str(), valid_str);
+
+    T::new_ref(invalid_str).expect_err("pass");
+
+    let y = unsafe { T::new_ref_unchecked(valid_str) };
+    assert_eq!(y.as_str(), valid_str);
+    assert_eq!(<T as AsRef<str>>::as_ref(&y), valid_str);
+}
+
+#[test]
+fn test_pneu_str_trait_value_string_f32() {
+    test_pneu_str_trait_case::<FreeStandingStr>("blah", "");
+}
+
+pneutype::pneutype_conformance!(
+    lowercase_conformance: Lowercase,
+    valid = ["", "abcd", "abcxyz"],
+    invalid = ["abcdE", "abcPqr", "abc xyz", "é"],
+    std_impls,
+    hash,
+    serde,
+    serde_borrowed,
+);
+
+pneutype::pneutype_conformance!(
+    url_conformance: URL,
+    valid = [
+        "https://no.golf:12345/at/all?abc=pqr#tag1",
+        "https://no.golf",
+        "file:///a/b/c",
+    ],
+    invalid = [
+        "",
+        "xyz",
+        "http::blah/123",
+        "http::/blah/123",
+        "blah/123?a=b",
+    ],
+    std_impls,
+);
+
+pneutype::pneutype_conformance!(
+    value_string_i32_conformance: ValueString<i32>,
+    valid = ["123", "-45"],
+    invalid = ["345.6", "abc"],
+    std_impls,
+    hash,
+    serde,
+    serde_borrowed,
+);
+
+pneutype::pneutype_conformance!(
+    value_string_f32_conformance: ValueString<f32>,
+    valid = ["12.25", "-0.5"],
+    invalid = ["", "abc"],
+    std_impls,
+    serde,
+    serde_borrowed,
+);
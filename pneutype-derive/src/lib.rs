@@ -1,6 +1,266 @@
-use darling::FromDeriveInput;
+use darling::{FromDeriveInput, FromMeta};
 use quote::quote;
 
+/// Specifies if and how serde::Deserialize is derived.  The bare `deserialize` attribute is the same as
+/// `deserialize = "strict"`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+enum DeserializeMode {
+    #[default]
+    None,
+    /// Validate using pneutype::Validate.
+    Strict,
+    /// Validate using pneutype::ValidateLenient and canonicalize into the strict form.
+    Lenient,
+    /// Normalize using pneutype::Normalize before validating.
+    Normalize,
+}
+
+impl FromMeta for DeserializeMode {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Self::Strict)
+    }
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "strict" => Ok(Self::Strict),
+            "lenient" => Ok(Self::Lenient),
+            "normalize" => Ok(Self::Normalize),
+            _ => Err(darling::Error::unknown_value(value)),
+        }
+    }
+}
+
+/// Specifies the description of what's accepted, used in the error messages of the derived serde::Deserialize.  The
+/// bare `expecting` attribute uses the description given by pneutype::Describe.
+#[derive(Clone, Debug, Default, PartialEq)]
+enum Expecting {
+    #[default]
+    None,
+    /// The given description.
+    Literal(String),
+    /// The description given by pneutype::Describe::description.
+    Describe,
+}
+
+impl FromMeta for Expecting {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Self::Describe)
+    }
+    fn from_string(value: &str) -> darling::Result<Self> {
+        Ok(Self::Literal(value.to_string()))
+    }
+}
+
+/// The `export_ts` attribute of `pneu_str`, which optionally names the TypeScript type, e.g. `export_ts = "Thingy"`.
+#[derive(Debug, Default)]
+struct ExportTs(Option<String>);
+
+impl FromMeta for ExportTs {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Self(None))
+    }
+    fn from_string(value: &str) -> darling::Result<Self> {
+        Ok(Self(Some(value.to_string())))
+    }
+}
+
+/// Returns true iff the name can be used as a TypeScript type name, i.e. it's an identifier that isn't a reserved
+/// word or a predefined type name (e.g. "string").
+fn is_ts_identifier(name: &str) -> bool {
+    const RESERVED: &[&str] = &[
+        "any",
+        "bigint",
+        "boolean",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "debugger",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "enum",
+        "export",
+        "extends",
+        "false",
+        "finally",
+        "for",
+        "function",
+        "if",
+        "implements",
+        "import",
+        "in",
+        "instanceof",
+        "interface",
+        "let",
+        "never",
+        "new",
+        "null",
+        "number",
+        "object",
+        "package",
+        "private",
+        "protected",
+        "public",
+        "return",
+        "static",
+        "string",
+        "super",
+        "switch",
+        "symbol",
+        "this",
+        "throw",
+        "true",
+        "try",
+        "typeof",
+        "undefined",
+        "unknown",
+        "var",
+        "void",
+        "while",
+        "with",
+        "yield",
+    ];
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+        && !RESERVED.contains(&name)
+}
+
+/// The `consts(NAME = "value", ...)` attribute of `pneu_str`, in the order given.
+#[derive(Debug, Default)]
+struct NamedConsts(Vec<(syn::Ident, String)>);
+
+impl FromMeta for NamedConsts {
+    fn from_list(items: &[syn::NestedMeta]) -> darling::Result<Self> {
+        let mut named_consts = Vec::with_capacity(items.len());
+        for item in items {
+            match item {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(lit_str),
+                    ..
+                })) if path.get_ident().is_some() => {
+                    named_consts.push((path.get_ident().unwrap().clone(), lit_str.value()));
+                }
+                _ => {
+                    return Err(
+                        darling::Error::custom("expected a list of NAME = \"value\" items")
+                            .with_span(item),
+                    )
+                }
+            }
+        }
+        Ok(Self(named_consts))
+    }
+}
+
+/// The `vis(method = "visibility", ...)` attribute, which overrides the (otherwise `pub`) visibility of the named
+/// generated inherent methods, e.g. `vis(new_unchecked = "pub(crate)")`.  An empty visibility makes the method
+/// private.  This doesn't affect the trait impls (e.g. pneutype::PneuString::new_unchecked), which are always public.
+#[derive(Debug, Default)]
+struct MethodVisibilities(Vec<(syn::Ident, syn::Visibility)>);
+
+impl FromMeta for MethodVisibilities {
+    fn from_list(items: &[syn::NestedMeta]) -> darling::Result<Self> {
+        let mut method_visibilities = Vec::with_capacity(items.len());
+        for item in items {
+            match item {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(lit_str),
+                    ..
+                })) if path.get_ident().is_some() => {
+                    let visibility = lit_str.parse().map_err(|_| {
+                        darling::Error::custom("expected a visibility, e.g. \"pub(crate)\"")
+                            .with_span(lit_str)
+                    })?;
+                    method_visibilities.push((path.get_ident().unwrap().clone(), visibility));
+                }
+                _ => {
+                    return Err(darling::Error::custom(
+                        "expected a list of method = \"visibility\" items",
+                    )
+                    .with_span(item))
+                }
+            }
+        }
+        Ok(Self(method_visibilities))
+    }
+}
+
+impl MethodVisibilities {
+    /// Returns the visibility of each of the given generated inherent methods, in the same order, panicking if any
+    /// other method was named.
+    fn resolve<const N: usize>(&self, methods: [&str; N]) -> [syn::Visibility; N] {
+        for (method, _) in &self.0 {
+            if !methods.iter().any(|name| method == name) {
+                panic!(
+                    "vis names unknown method {:?}; expected one of {:?}",
+                    method.to_string(),
+                    methods
+                );
+            }
+        }
+        methods.map(|name| {
+            self.0
+                .iter()
+                .rev()
+                .find(|(method, _)| method == name)
+                .map_or_else(
+                    || syn::parse_quote! { pub },
+                    |(_, visibility)| visibility.clone(),
+                )
+        })
+    }
+}
+
+/// The `one_of("value", ...)` attribute of `pneu_str`, in the order given.
+#[derive(Debug, Default)]
+struct OneOf(Vec<String>);
+
+impl FromMeta for OneOf {
+    fn from_list(items: &[syn::NestedMeta]) -> darling::Result<Self> {
+        let mut values = Vec::with_capacity(items.len());
+        for item in items {
+            match item {
+                syn::NestedMeta::Lit(syn::Lit::Str(lit_str)) => values.push(lit_str.value()),
+                _ => {
+                    return Err(darling::Error::custom("expected a list of string literals")
+                        .with_span(item))
+                }
+            }
+        }
+        Ok(Self(values))
+    }
+}
+
+/// The `consts(NAME, ...)` attribute of `pneu_string`, which names consts defined on the PneuStr, in the order
+/// given.
+#[derive(Debug, Default)]
+struct ConstNames(Vec<syn::Ident>);
+
+impl FromMeta for ConstNames {
+    fn from_list(items: &[syn::NestedMeta]) -> darling::Result<Self> {
+        let mut const_names = Vec::with_capacity(items.len());
+        for item in items {
+            match item {
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.get_ident().is_some() => {
+                    const_names.push(path.get_ident().unwrap().clone());
+                }
+                _ => {
+                    return Err(
+                        darling::Error::custom("expected a list of const names").with_span(item)
+                    )
+                }
+            }
+        }
+        Ok(Self(const_names))
+    }
+}
+
 //
 // proc_macro for creating a String-based newtype
 //
@@ -10,10 +270,14 @@ use quote::quote;
 struct PneuStringArguments {
     /// Specify the PneuStr analog to this PneuString.  This will define the target of std::borrow::Borrow and std::ops::Deref.
     borrow: String,
-    /// Specify true to derive an implementation of serde::Deserialize.  The `serde` crate must be imported into
-    /// the crate in which this PneuString is defined in order for this to work.  Using this attribute is optional,
-    /// and a manual implementation of serde::Deserialize is of course possible.
-    deserialize: bool,
+    /// Specify `deserialize` (or equivalently `deserialize = "strict"`) to derive an implementation of
+    /// serde::Deserialize.  The `serde` crate must be imported into the crate in which this PneuString is defined
+    /// in order for this to work.  Using this attribute is optional, and a manual implementation of
+    /// serde::Deserialize is of course possible.  Specify `deserialize = "lenient"` to instead derive an
+    /// implementation that uses `from_legacy`, i.e. which accepts legacy forms and canonicalizes them (this requires
+    /// the PneuStr to implement pneutype::ValidateLenient).  Specify `deserialize = "normalize"` to instead derive an
+    /// implementation that uses `try_normalize` (this requires the PneuStr to implement pneutype::Normalize).
+    deserialize: DeserializeMode,
     /// Specify true to derive an implementation of serde::Serialize.  The `serde` crate must be imported into
     /// the crate in which this PneuString is defined in order for this to work.  Using this attribute is optional,
     /// and a manual implementation of serde::Serialize is of course possible.  However, in the case of a PneuString
@@ -28,6 +292,72 @@ struct PneuStringArguments {
     /// would be used in the case of a PneuString having generics, e.g.
     /// `#[derive(pneutype::PneuString)] #[pneu_string(string_field = "s")] pub struct ThingString<T> { t: std::marker::PhantomData<T>, s: String }`
     string_field: Option<String>,
+    /// Optionally specify a human-readable description of what this PneuString accepts, for use in the error
+    /// messages of the derived implementation of serde::Deserialize, e.g. `expecting = "all-lowercase ASCII"`
+    /// gives `invalid value: string "Foo", expected a Lowercase (all-lowercase ASCII)`.  The bare `expecting`
+    /// attribute uses the description given by pneutype::Describe instead.  If not specified, then the validation
+    /// error is used in its place.
+    expecting: Expecting,
+    /// Optionally name consts defined on the PneuStr via its `consts` attribute, e.g. `consts(DEFAULT, ADMIN)`, for
+    /// each of which a constructor is generated, e.g. `default_value()` and `admin_value()`.
+    consts: ConstNames,
+    /// Optionally name a const defined on the PneuStr (e.g. `default = "DEFAULT"`) to implement std::default::Default
+    /// with.
+    default: Option<String>,
+    /// Optionally specify the parent PneuString that this PneuString refines, e.g. `refines = "Lowercase"`, where
+    /// this PneuString's PneuStr must refine the parent's PneuStr (see pneutype::Refines).  This generates From
+    /// for the parent and TryFrom from the parent, which reuse the String allocation.  Borrowed upward conversions
+    /// are via the PneuStr, e.g. `slug.as_pneu_str().as_ref()`.
+    refines: Option<String>,
+    /// Optionally specify the name of an error type to generate, e.g. `error = "InvalidLowercase"`, which is then
+    /// used as the error of the std::str::FromStr, TryFrom<&str> and TryFrom<String> impls instead of the
+    /// validation error.  It holds the rejected `input` and the validation error as `source`, and implements
+    /// std::error::Error, so that it works with `?` into e.g. `Box<dyn std::error::Error>`.  Its `source()` is the
+    /// validation error if that implements std::error::Error too.
+    error: Option<String>,
+    /// Specify to implement miette::Diagnostic for the error type named by `error`, using the rejected input as the
+    /// source code and forwarding everything else to the validation error, which must implement miette::Diagnostic
+    /// (e.g. pneutype::ValidationError).  Requires the `miette` feature of pneutype.
+    diagnostic: bool,
+    /// Specify to not derive pneutype::Describe, which otherwise delegates to that of the PneuStr (apart from the
+    /// type name), e.g. in order to implement it manually with different bounds.
+    no_describe: bool,
+    /// Specify to not derive schemars::JsonSchema and utoipa::ToSchema (with the `schemars` and `utoipa` features of
+    /// pneutype), e.g. in order to implement them manually.
+    no_schema: bool,
+    /// Specify to not derive proptest::arbitrary::Arbitrary and arbitrary::Arbitrary (with the `proptest` and
+    /// `arbitrary` features of pneutype), e.g. in order to implement them manually.
+    no_arbitrary: bool,
+    /// Specify to implement Eq, Ord and Hash consistently with those of str (as std::borrow::Borrow<str> requires),
+    /// and PartialEq and PartialOrd between this PneuString and its PneuStr, &PneuStr, str, &str, String and
+    /// Cow<str>, in both directions.  These must then not be derived.
+    str_cmp: bool,
+    /// Specify to not implement std::ops::Deref (to the PneuStr).  This PneuString then doesn't implement
+    /// pneutype::PneuStringExt.
+    no_deref: bool,
+    /// Specify to not implement std::fmt::Display, e.g. for secrets.  This PneuString then doesn't implement
+    /// pneutype::PneuStringExt.
+    no_display: bool,
+    /// Specify to not implement std::str::FromStr, e.g. in order to implement it manually.  This PneuString then
+    /// doesn't implement pneutype::PneuStringExt.
+    no_from_str: bool,
+    /// Optionally override the visibility of generated inherent methods, e.g. `vis(new_unchecked = "pub(crate)")`.
+    /// These can be any of new_unchecked, new_unchecked_const, try_from_collect_errors, try_from_with, from_legacy,
+    /// normalize_from, try_normalize, sanitize, sanitize_with_report, as_pneu_str (regardless of its name),
+    /// as_str and into_string.  Only the inherent methods are restricted, so this can't restrict new_unchecked,
+    /// which remains public as the trait method pneutype::PneuString::new_unchecked.
+    #[darling(rename = "vis")]
+    method_vis: MethodVisibilities,
+    /// Specify a function `fn() -> proptest::strategy::BoxedStrategy<String>` generating candidates for the
+    /// generated impl of proptest::arbitrary::Arbitrary (with the `proptest` feature of pneutype), e.g.
+    /// `proptest_seed = "thingy_candidates"`.  This is required for constraints that can't be generated from (i.e.
+    /// pneutype::Constraint::Custom), and its absence fails the build if pneutype::Describe::CONSTRAINT_IS_CUSTOM
+    /// says so.  Invalid candidates are rejected.
+    proptest_seed: Option<String>,
+    /// Specify a function of type pneutype::generate::ArbitrarySeed generating candidates for the generated impl
+    /// of arbitrary::Arbitrary (with the `arbitrary` feature of pneutype), analogous to `proptest_seed`, except
+    /// that it's also required for pneutype::Constraint::Pattern.
+    arbitrary_seed: Option<String>,
 }
 
 #[proc_macro_derive(PneuString, attributes(pneu_string))]
@@ -65,7 +395,7 @@ pub fn derive_pneu_string(token_stream: proc_macro::TokenStream) -> proc_macro::
     let string_field: syn::Expr =
         syn::parse_str(pneu_string_arguments.string_field.as_deref().unwrap_or("0")).unwrap();
 
-    let serde_deserialize_maybe = if pneu_string_arguments.deserialize {
+    let serde_deserialize_maybe = if pneu_string_arguments.deserialize != DeserializeMode::None {
         // Create new lifetime parameters 'de and 'a
         let lifetime_de = syn::Lifetime::new("'de", proc_macro2::Span::call_site());
         let lifetime_a = syn::Lifetime::new("'a", proc_macro2::Span::call_site());
@@ -117,6 +447,93 @@ pub fn derive_pneu_string(token_stream: proc_macro::TokenStream) -> proc_macro::
             _serde_deserialize_visitor_where_clause,
         ) = serde_deserialize_visitor_generics.split_for_impl();
 
+        let describe_type = quote! { #pneu_string_name #pneu_string_type_generics };
+        let serde_expecting = serde_expected(
+            &pneu_string_name,
+            &describe_type,
+            &pneu_string_arguments.expecting,
+            "",
+        );
+        let serde_invalid_value_arm_v = serde_invalid_value_arm(
+            &pneu_string_name,
+            &describe_type,
+            &pneu_string_arguments.expecting,
+            quote! { serde::de::Unexpected::Str(v) },
+        );
+        let serde_invalid_value_arm_v_as_str = serde_invalid_value_arm(
+            &pneu_string_name,
+            &describe_type,
+            &pneu_string_arguments.expecting,
+            quote! { serde::de::Unexpected::Str(v.as_str()) },
+        );
+        let serde_invalid_value_arm_consumed = serde_invalid_value_arm(
+            &pneu_string_name,
+            &describe_type,
+            &pneu_string_arguments.expecting,
+            quote! { serde::de::Unexpected::Other("string") },
+        );
+        let serde_visit_str_and_string = match pneu_string_arguments.deserialize {
+            DeserializeMode::None => unreachable!(),
+            DeserializeMode::Strict => quote! {
+                fn visit_str<E>(self, v: &str) -> std::result::Result<Self::Value, E>
+                where
+                    E: serde::de::Error,
+                {
+                    match <#pneu_str_name #pneu_string_type_generics as pneutype::Validate>::validate(v) {
+                        Ok(()) => Ok(unsafe { #pneu_string_name::new_unchecked(v.to_string()) }),
+                        #serde_invalid_value_arm_v
+                    }
+                }
+                fn visit_string<E>(self, v: String) -> std::result::Result<Self::Value, E>
+                where
+                    E: serde::de::Error,
+                {
+                    match <#pneu_str_name #pneu_string_type_generics as pneutype::Validate>::validate(v.as_str()) {
+                        Ok(()) => Ok(unsafe { #pneu_string_name::new_unchecked(v) }),
+                        #serde_invalid_value_arm_v_as_str
+                    }
+                }
+            },
+            DeserializeMode::Lenient => quote! {
+                fn visit_str<E>(self, v: &str) -> std::result::Result<Self::Value, E>
+                where
+                    E: serde::de::Error,
+                {
+                    match #pneu_string_name::from_legacy(v) {
+                        Ok(value) => Ok(value),
+                        #serde_invalid_value_arm_v
+                    }
+                }
+                fn visit_string<E>(self, v: String) -> std::result::Result<Self::Value, E>
+                where
+                    E: serde::de::Error,
+                {
+                    self.visit_str(v.as_str())
+                }
+            },
+            DeserializeMode::Normalize => quote! {
+                fn visit_str<E>(self, v: &str) -> std::result::Result<Self::Value, E>
+                where
+                    E: serde::de::Error,
+                {
+                    match #pneu_string_name::normalize_from(v) {
+                        Ok(value) => Ok(value),
+                        #serde_invalid_value_arm_v
+                    }
+                }
+                fn visit_string<E>(self, v: String) -> std::result::Result<Self::Value, E>
+                where
+                    E: serde::de::Error,
+                {
+                    // try_normalize reuses v's allocation, so v is gone by the time the error is reported.
+                    match #pneu_string_name::try_normalize(v) {
+                        Ok(value) => Ok(value),
+                        #serde_invalid_value_arm_consumed
+                    }
+                }
+            },
+        };
+
         quote! {
             impl #serde_deserialize_impl_generics serde::Deserialize<#lifetime_de> for #pneu_string_name #pneu_string_type_generics #pneu_string_where_clause {
                 fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
@@ -129,20 +546,9 @@ pub fn derive_pneu_string(token_stream: proc_macro::TokenStream) -> proc_macro::
                         type Value = #pneu_string_name #pneu_string_type_generics;
 
                         fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                            formatter.write_str("a string")
-                        }
-                        fn visit_str<E>(self, v: &str) -> std::result::Result<Self::Value, E>
-                        where
-                            E: serde::de::Error,
-                        {
-                            #pneu_string_name::try_from(v).map_err(serde::de::Error::custom)
-                        }
-                        fn visit_string<E>(self, v: String) -> std::result::Result<Self::Value, E>
-                        where
-                            E: serde::de::Error,
-                        {
-                            #pneu_string_name::try_from(v).map_err(serde::de::Error::custom)
+                            #serde_expecting
                         }
+                        #serde_visit_str_and_string
                     }
 
                     deserializer.deserialize_string(#serde_deserialize_visitor_construction)
@@ -174,40 +580,481 @@ pub fn derive_pneu_string(token_stream: proc_macro::TokenStream) -> proc_macro::
         syn::parse_str("as_pneu_str").unwrap()
     };
 
+    let const_constructors = pneu_string_arguments
+        .consts
+        .0
+        .iter()
+        .map(|const_name| {
+            let constructor_name =
+                quote::format_ident!("{}_value", const_name.to_string().to_lowercase());
+            let doc = format!(
+                "Construct this PneuString from {}::{}.",
+                pneu_str_name, const_name
+            );
+            quote! {
+                #[doc = #doc]
+                pub fn #constructor_name() -> Self {
+                    Self::from(<#pneu_str_name #pneu_string_type_generics>::#const_name)
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+    let default_maybe = match pneu_string_arguments.default.as_deref() {
+        Some(const_name) => {
+            let const_name = syn::Ident::new(const_name, proc_macro2::Span::call_site());
+            quote! {
+                impl #pneu_string_impl_generics std::default::Default for #pneu_string_name #pneu_string_type_generics #pneu_string_where_clause {
+                    fn default() -> Self {
+                        Self::from(<#pneu_str_name #pneu_string_type_generics>::#const_name)
+                    }
+                }
+            }
+        }
+        None => quote! {},
+    };
+
+    let refines_maybe = match pneu_string_arguments.refines.as_deref() {
+        Some(refines) => {
+            let parent_type = syn::parse_str::<syn::Type>(refines)
+                .unwrap_or_else(|_| panic!("refines = {:?} is not a type", refines));
+            quote! {
+                impl #pneu_string_impl_generics From<#pneu_string_name #pneu_string_type_generics> for #parent_type #pneu_string_where_clause {
+                    fn from(s: #pneu_string_name #pneu_string_type_generics) -> Self {
+                        // Safe because every valid value of a refinement is valid for its parent.
+                        unsafe { <#parent_type as pneutype::PneuString>::new_unchecked(s.into_string()) }
+                    }
+                }
+
+                impl #pneu_string_impl_generics TryFrom<#parent_type> for #pneu_string_name #pneu_string_type_generics #pneu_string_where_clause {
+                    type Error = <#pneu_str_name #pneu_string_type_generics as pneutype::Validate>::Error;
+                    fn try_from(parent: #parent_type) -> std::result::Result<Self, Self::Error> {
+                        // Only the refinement needs to be checked.
+                        <#pneu_str_name #pneu_string_type_generics as pneutype::Refines<<#parent_type as pneutype::PneuString>::Borrowed>>::validate_refinement(
+                            <#parent_type as pneutype::AsStr>::as_str(&parent)
+                        )?;
+                        Ok(unsafe { Self::new_unchecked(<#parent_type as pneutype::PneuString>::into_string(parent)) })
+                    }
+                }
+            }
+        }
+        None => quote! {},
+    };
+
+    if pneu_string_arguments.diagnostic && pneu_string_arguments.error.is_none() {
+        panic!("diagnostic requires error = \"...\"");
+    }
+    let (error_wrapper_maybe, conversion_error, validate_str, validate_string) =
+        match pneu_string_arguments.error.as_deref() {
+            Some(error_name) => {
+                if pneu_string_has_generics {
+                    panic!("error is not supported for a PneuString with generics");
+                }
+                let error_name = syn::Ident::new(error_name, proc_macro2::Span::call_site());
+                (
+                    error_wrapper(
+                        &input.vis,
+                        &error_name,
+                        &pneu_string_name,
+                        &pneu_str_name,
+                        pneu_string_arguments.diagnostic,
+                    ),
+                    quote! { #error_name },
+                    quote! {
+                        <#pneu_str_name as pneutype::Validate>::validate(s).map_err(|source| #error_name { input: s.to_string(), source })?;
+                    },
+                    quote! {
+                        if let Err(source) = <#pneu_str_name as pneutype::Validate>::validate(s.as_str()) {
+                            return Err(#error_name { input: s, source });
+                        }
+                    },
+                )
+            }
+            None => (
+                quote! {},
+                quote! { <#pneu_str_name #pneu_string_type_generics as pneutype::Validate>::Error },
+                quote! {
+                    <#pneu_str_name #pneu_string_type_generics as pneutype::Validate>::validate(s)?;
+                },
+                quote! {
+                    <#pneu_str_name #pneu_string_type_generics as pneutype::Validate>::validate(s.as_str())?;
+                },
+            ),
+        };
+
+    let describe_maybe = if pneu_string_arguments.no_describe {
+        quote! {}
+    } else {
+        let type_name = pneu_string_name.to_string();
+        let describe_expecting = describe_expecting(&pneu_string_arguments.expecting);
+        let mut describe_where_clause = input.generics.clone().make_where_clause().clone();
+        describe_where_clause.predicates.push(hrtb_bound(
+            quote! { #pneu_str_name #pneu_string_type_generics },
+            quote! { pneutype::Describe },
+        ));
+        quote! {
+            impl #pneu_string_impl_generics pneutype::Describe for #pneu_string_name #pneu_string_type_generics #describe_where_clause {
+                const TYPE_NAME: &'static str = #type_name;
+                const CONSTRAINT_IS_CUSTOM: bool = <#pneu_str_name #pneu_string_type_generics as pneutype::Describe>::CONSTRAINT_IS_CUSTOM;
+                fn constraint() -> pneutype::Constraint {
+                    <#pneu_str_name #pneu_string_type_generics as pneutype::Describe>::constraint()
+                }
+                fn description() -> std::borrow::Cow<'static, str> {
+                    <#pneu_str_name #pneu_string_type_generics as pneutype::Describe>::description()
+                }
+                #describe_expecting
+            }
+        }
+    };
+
+    let json_schema_maybe = if cfg!(feature = "schemars") && !pneu_string_arguments.no_schema {
+        let mut json_schema_where_clause = input.generics.clone().make_where_clause().clone();
+        json_schema_where_clause.predicates.push(hrtb_bound(
+            quote! { #pneu_str_name #pneu_string_type_generics },
+            quote! { pneutype::schemars::JsonSchema },
+        ));
+        // The PneuString has the same schema as its PneuStr.
+        quote! {
+            impl #pneu_string_impl_generics pneutype::schemars::JsonSchema for #pneu_string_name #pneu_string_type_generics #json_schema_where_clause {
+                fn inline_schema() -> bool {
+                    <#pneu_str_name #pneu_string_type_generics as pneutype::schemars::JsonSchema>::inline_schema()
+                }
+                fn schema_name() -> std::borrow::Cow<'static, str> {
+                    <#pneu_str_name #pneu_string_type_generics as pneutype::schemars::JsonSchema>::schema_name()
+                }
+                fn schema_id() -> std::borrow::Cow<'static, str> {
+                    <#pneu_str_name #pneu_string_type_generics as pneutype::schemars::JsonSchema>::schema_id()
+                }
+                fn json_schema(generator: &mut pneutype::schemars::SchemaGenerator) -> pneutype::schemars::Schema {
+                    <#pneu_str_name #pneu_string_type_generics as pneutype::schemars::JsonSchema>::json_schema(generator)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let openapi_schema_maybe = if cfg!(feature = "utoipa") && !pneu_string_arguments.no_schema {
+        let mut openapi_schema_where_clause = input.generics.clone().make_where_clause().clone();
+        openapi_schema_where_clause.predicates.push(hrtb_bound(
+            quote! { #pneu_str_name #pneu_string_type_generics },
+            quote! { pneutype::utoipa::ToSchema },
+        ));
+        // The PneuString has the same schema as its PneuStr.
+        quote! {
+            impl #pneu_string_impl_generics pneutype::utoipa::PartialSchema for #pneu_string_name #pneu_string_type_generics #openapi_schema_where_clause {
+                fn schema() -> pneutype::utoipa::openapi::RefOr<pneutype::utoipa::openapi::schema::Schema> {
+                    <#pneu_str_name #pneu_string_type_generics as pneutype::utoipa::PartialSchema>::schema()
+                }
+            }
+
+            impl #pneu_string_impl_generics pneutype::utoipa::ToSchema for #pneu_string_name #pneu_string_type_generics #openapi_schema_where_clause {
+                fn name() -> std::borrow::Cow<'static, str> {
+                    <#pneu_str_name #pneu_string_type_generics as pneutype::utoipa::ToSchema>::name()
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let [new_unchecked_vis, new_unchecked_const_vis, try_from_collect_errors_vis, try_from_with_vis, from_legacy_vis, normalize_from_vis, try_normalize_vis, sanitize_vis, sanitize_with_report_vis, as_pneu_str_vis, as_str_vis, into_string_vis] =
+        pneu_string_arguments.method_vis.resolve([
+            "new_unchecked",
+            "new_unchecked_const",
+            "try_from_collect_errors",
+            "try_from_with",
+            "from_legacy",
+            "normalize_from",
+            "try_normalize",
+            "sanitize",
+            "sanitize_with_report",
+            "as_pneu_str",
+            "as_str",
+            "into_string",
+        ]);
+
+    let deref_maybe = if pneu_string_arguments.no_deref {
+        quote! {}
+    } else {
+        quote! {
+            impl #pneu_string_impl_generics std::ops::Deref for #pneu_string_name #pneu_string_type_generics #pneu_string_where_clause {
+                type Target = #pneu_str_name #pneu_string_type_generics;
+                fn deref(&self) -> &Self::Target {
+                    Self::#as_pneu_str(self)
+                }
+            }
+        }
+    };
+
+    let display_maybe = if pneu_string_arguments.no_display {
+        quote! {}
+    } else {
+        quote! {
+            impl #pneu_string_impl_generics std::fmt::Display for #pneu_string_name #pneu_string_type_generics #pneu_string_where_clause {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+                    Self::as_str(self).fmt(f)
+                }
+            }
+        }
+    };
+
+    let from_str_maybe = if pneu_string_arguments.no_from_str {
+        quote! {}
+    } else {
+        quote! {
+            impl #pneu_string_impl_generics std::str::FromStr for #pneu_string_name #pneu_string_type_generics #pneu_string_where_clause {
+                type Err = #conversion_error;
+                fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+                    #validate_str
+                    let s = s.to_string();
+                    Ok(#self_construction)
+                }
+            }
+        }
+    };
+
+    let pneu_string_trait = quote! {
+        impl #pneu_string_impl_generics pneutype::PneuString for #pneu_string_name #pneu_string_type_generics #pneu_string_where_clause {
+            type Borrowed = #pneu_str_name #pneu_string_type_generics;
+            type TryFromStringErr = <Self as TryFrom<String>>::Error;
+            unsafe fn new_unchecked(s: String) -> Self {
+                Self::new_unchecked(s)
+            }
+            fn as_pneu_str(&self) -> &Self::Borrowed {
+                self.#as_pneu_str()
+            }
+            fn into_string(self) -> String {
+                self.into_string()
+            }
+        }
+    };
+
+    let str_cmp_maybe = if pneu_string_arguments.str_cmp {
+        let pneu_str_type = quote! { #pneu_str_name #pneu_string_type_generics };
+        str_cmp_impls(
+            &input.generics,
+            &quote! { #pneu_string_name #pneu_string_type_generics },
+            &[
+                quote! { #pneu_str_type },
+                quote! { &'__cmp #pneu_str_type },
+                quote! { str },
+                quote! { &'__cmp str },
+                quote! { String },
+                quote! { std::borrow::Cow<'__cmp, str> },
+            ],
+        )
+    } else {
+        quote! {}
+    };
+
+    let proptest_arbitrary_maybe = if cfg!(feature = "proptest")
+        && !pneu_string_arguments.no_arbitrary
+    {
+        let mut proptest_arbitrary_where_clause =
+            input.generics.clone().make_where_clause().clone();
+        // A PneuString not implementing Debug simply doesn't implement Arbitrary.
+        proptest_arbitrary_where_clause.predicates.push(hrtb_bound(
+            quote! { #pneu_string_name #pneu_string_type_generics },
+            quote! { 'static + std::fmt::Debug },
+        ));
+        proptest_arbitrary_where_clause.predicates.push(hrtb_bound(
+            quote! { #pneu_str_name #pneu_string_type_generics },
+            quote! { 'static + pneutype::Validate<Data = str> + pneutype::Describe },
+        ));
+        let seed = match pneu_string_arguments.proptest_seed.as_deref() {
+            Some(proptest_seed) => {
+                let proptest_seed: syn::Path = syn::parse_str(proptest_seed).unwrap();
+                quote! { Some(#proptest_seed()) }
+            }
+            None => quote! {
+                {
+                    // Fails the build if the constraint is known to be one that values can't be generated from.
+                    let () = pneutype::generate::RequireSeed::<#pneu_str_name #pneu_string_type_generics>::PROPTEST;
+                    None
+                }
+            },
+        };
+        quote! {
+            impl #pneu_string_impl_generics pneutype::proptest::arbitrary::Arbitrary for #pneu_string_name #pneu_string_type_generics #proptest_arbitrary_where_clause {
+                type Parameters = ();
+                type Strategy = pneutype::proptest::strategy::BoxedStrategy<Self>;
+                fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+                    use pneutype::proptest::strategy::Strategy;
+                    pneutype::generate::proptest_strategy::<#pneu_str_name #pneu_string_type_generics>(#seed)
+                        // The generated strings are already validated.
+                        .prop_map(|s| unsafe { Self::new_unchecked(s) })
+                        .boxed()
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let arbitrary_arbitrary_maybe = if cfg!(feature = "arbitrary")
+        && !pneu_string_arguments.no_arbitrary
+    {
+        let lifetime_a = syn::Lifetime::new("'__a", proc_macro2::Span::call_site());
+        let mut arbitrary_generics = input.generics.clone();
+        arbitrary_generics.params.insert(
+            0,
+            syn::GenericParam::Lifetime(syn::LifetimeDef::new(lifetime_a.clone())),
+        );
+        let (arbitrary_impl_generics, _arbitrary_type_generics, _arbitrary_where_clause) =
+            arbitrary_generics.split_for_impl();
+        let mut arbitrary_where_clause = input.generics.clone().make_where_clause().clone();
+        arbitrary_where_clause.predicates.push(hrtb_bound(
+            quote! { #pneu_str_name #pneu_string_type_generics },
+            quote! { pneutype::Validate<Data = str> + pneutype::Describe },
+        ));
+        let seed = match pneu_string_arguments.arbitrary_seed.as_deref() {
+            Some(arbitrary_seed) => {
+                let arbitrary_seed: syn::Path = syn::parse_str(arbitrary_seed).unwrap();
+                quote! { Some(#arbitrary_seed) }
+            }
+            None => quote! {
+                {
+                    // Fails the build if the constraint is known to be one that values can't be generated from.
+                    let () = pneutype::generate::RequireSeed::<#pneu_str_name #pneu_string_type_generics>::ARBITRARY;
+                    None
+                }
+            },
+        };
+        quote! {
+            impl #arbitrary_impl_generics pneutype::arbitrary::Arbitrary<#lifetime_a> for #pneu_string_name #pneu_string_type_generics #arbitrary_where_clause {
+                fn arbitrary(u: &mut pneutype::arbitrary::Unstructured<#lifetime_a>) -> pneutype::arbitrary::Result<Self> {
+                    let s = pneutype::generate::arbitrary_string::<#pneu_str_name #pneu_string_type_generics>(u, #seed)?;
+                    // The generated string is already validated.
+                    Ok(unsafe { Self::new_unchecked(s) })
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // The inherent methods that are also provided by the pneutype::PneuString trait delegate to it, and those that
+    // depend on an optional trait of the PneuStr are only available if it's implemented.
+    let pneu_str_is_validate_lenient = hrtb_bound(
+        quote! { #pneu_str_name #pneu_string_type_generics },
+        quote! { pneutype::ValidateLenient },
+    );
+    let pneu_str_is_normalize = hrtb_bound(
+        quote! { #pneu_str_name #pneu_string_type_generics },
+        quote! { pneutype::Normalize },
+    );
+    let pneu_str_is_sanitize = hrtb_bound(
+        quote! { #pneu_str_name #pneu_string_type_generics },
+        quote! { pneutype::Sanitize },
+    );
+
     let output = quote! {
+        #refines_maybe
+
+        #error_wrapper_maybe
+
+        #describe_maybe
+
+        #json_schema_maybe
+
+        #openapi_schema_maybe
+
+        #str_cmp_maybe
+
+        #proptest_arbitrary_maybe
+
+        #arbitrary_arbitrary_maybe
+
         impl #pneu_string_impl_generics #pneu_string_name #pneu_string_type_generics #pneu_string_where_clause {
+            #(#const_constructors)*
             /// Unsafe: Construct this PneuString where the input is already guaranteed (by the caller) to be valid.
             /// However, a debug_assert! will be used to check the validity condition.  For a const version of this,
             /// see new_unchecked_const.
-            pub unsafe fn new_unchecked(s: String) -> Self {
+            #new_unchecked_vis unsafe fn new_unchecked(s: String) -> Self {
                 debug_assert!(<#pneu_str_name #pneu_string_type_generics as pneutype::Validate>::validate(s.as_str()).is_ok(), "programmer error: new_unchecked was passed invalid data");
                 #self_construction
             }
             /// Unsafe: Construct this PneuString where the input is already guaranteed (by the caller) to be valid.
             /// Because this is a const function, the validity condition can't be checked in a debug_assert! as it
             /// is in new_ref_unchecked.
-            pub const unsafe fn new_unchecked_const(s: String) -> Self {
+            #new_unchecked_const_vis const unsafe fn new_unchecked_const(s: String) -> Self {
                 #self_construction
             }
+            /// Validate the given string, reporting every failure instead of just the first (see
+            /// pneutype::Validate::validate_all), and construct this PneuString from it.  The string is only
+            /// converted into a String if it's valid.
+            #try_from_collect_errors_vis fn try_from_collect_errors<S>(s: S) -> std::result::Result<Self, Vec<<#pneu_str_name #pneu_string_type_generics as pneutype::Validate>::Error>>
+            where
+                S: AsRef<str> + Into<String>,
+            {
+                <Self as pneutype::PneuString>::try_from_collect_errors(s)
+            }
+            /// Validate the given string, including against the given runtime context (see pneutype::ValidateWith),
+            /// and construct this PneuString from it.  The string is only converted into a String if it's valid.
+            #try_from_with_vis fn try_from_with<S, Ctx>(s: S, ctx: &Ctx) -> std::result::Result<Self, <#pneu_str_name #pneu_string_type_generics as pneutype::Validate>::Error>
+            where
+                S: AsRef<str> + Into<String>,
+                Ctx: ?Sized,
+                #pneu_str_name #pneu_string_type_generics: pneutype::Validate<Data = str> + pneutype::ValidateWith<Ctx>,
+            {
+                <Self as pneutype::PneuString>::try_from_with(s, ctx)
+            }
+            /// Validate the given string in lenient mode (see pneutype::ValidateLenient), canonicalize it into the
+            /// strict form, and construct this PneuString from that.  The strict form is validated as well.
+            #from_legacy_vis fn from_legacy<S>(s: S) -> std::result::Result<Self, <#pneu_str_name #pneu_string_type_generics as pneutype::Validate>::Error>
+            where
+                S: AsRef<str> + Into<String>,
+                #pneu_str_is_validate_lenient,
+            {
+                <Self as pneutype::PneuString>::from_legacy(s)
+            }
+            /// Normalize the given string (see pneutype::Normalize), validate the normalized form, and construct this
+            /// PneuString from that.
+            #normalize_from_vis fn normalize_from(s: &str) -> std::result::Result<Self, <#pneu_str_name #pneu_string_type_generics as pneutype::Validate>::Error>
+            where
+                #pneu_str_is_normalize,
+            {
+                <Self as pneutype::PneuString>::normalize_from(s)
+            }
+            /// Same as normalize_from, but reuses the given String's allocation where possible.
+            #try_normalize_vis fn try_normalize(s: String) -> std::result::Result<Self, <#pneu_str_name #pneu_string_type_generics as pneutype::Validate>::Error>
+            where
+                #pneu_str_is_normalize,
+            {
+                <Self as pneutype::PneuString>::try_normalize(s)
+            }
+            /// Repair the given string into a valid form (see pneutype::Sanitize) and construct this PneuString from
+            /// that.  This panics if the Sanitize impl produces invalid data, since that's a bug in the impl.
+            #sanitize_vis fn sanitize(s: &str) -> Self
+            where
+                #pneu_str_is_sanitize,
+            {
+                <Self as pneutype::PneuString>::sanitize(s)
+            }
+            /// Same as sanitize, but also returns a report of what was changed.
+            #sanitize_with_report_vis fn sanitize_with_report(s: &str) -> (Self, pneutype::SanitizeReport)
+            where
+                #pneu_str_is_sanitize,
+            {
+                <Self as pneutype::PneuString>::sanitize_with_report(s)
+            }
             /// Return self as a reference to the associated PneuStr, i.e. a strongly-typed version of as_str.
-            pub fn #as_pneu_str(&self) -> &#pneu_str_name #pneu_string_type_generics {
-                use std::ops::Deref;
-                self.deref()
+            #as_pneu_str_vis fn #as_pneu_str(&self) -> &#pneu_str_name #pneu_string_type_generics {
+                unsafe { <#pneu_str_name #pneu_string_type_generics as pneutype::NewRefUnchecked>::new_ref_unchecked(self.#string_field.as_str()) }
             }
             /// Return a &str to the underlying String.
-            pub fn as_str(&self) -> &str {
+            #as_str_vis fn as_str(&self) -> &str {
                 self.#string_field.as_str()
             }
             /// Dissolve this instance and take the underlying String.
-            pub fn into_string(self) -> String {
+            #into_string_vis fn into_string(self) -> String {
                 self.#string_field
             }
         }
 
         impl #pneu_string_impl_generics std::convert::AsRef<#pneu_str_name #pneu_string_type_generics> for #pneu_string_name #pneu_string_type_generics #pneu_string_where_clause {
             fn as_ref(&self) -> &#pneu_str_name #pneu_string_type_generics {
-                use std::ops::Deref;
-                self.deref()
+                Self::#as_pneu_str(self)
             }
         }
 
@@ -225,8 +1072,7 @@ pub fn derive_pneu_string(token_stream: proc_macro::TokenStream) -> proc_macro::
 
         impl #pneu_string_impl_generics std::borrow::Borrow<#pneu_str_name #pneu_string_type_generics> for #pneu_string_name #pneu_string_type_generics #pneu_string_where_clause {
             fn borrow(&self) -> &#pneu_str_name #pneu_string_type_generics {
-                use std::ops::Deref;
-                self.deref()
+                Self::#as_pneu_str(self)
             }
         }
 
@@ -236,20 +1082,13 @@ pub fn derive_pneu_string(token_stream: proc_macro::TokenStream) -> proc_macro::
             }
         }
 
-        impl #pneu_string_impl_generics std::ops::Deref for #pneu_string_name #pneu_string_type_generics #pneu_string_where_clause {
-            type Target = #pneu_str_name #pneu_string_type_generics;
-            fn deref(&self) -> &Self::Target {
-                unsafe { #pneu_str_name::new_ref_unchecked(self.#string_field.as_str()) }
-            }
-        }
+        #deref_maybe
 
         #serde_deserialize_maybe
 
-        impl #pneu_string_impl_generics std::fmt::Display for #pneu_string_name #pneu_string_type_generics #pneu_string_where_clause {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
-                Self::as_str(self).fmt(f)
-            }
-        }
+        #default_maybe
+
+        #display_maybe
 
         impl #pneu_string_impl_generics From<&#pneu_str_name #pneu_string_type_generics> for #pneu_string_name #pneu_string_type_generics #pneu_string_where_clause {
             fn from(s: &#pneu_str_name #pneu_string_type_generics) -> Self {
@@ -258,54 +1097,33 @@ pub fn derive_pneu_string(token_stream: proc_macro::TokenStream) -> proc_macro::
             }
         }
 
-        impl #pneu_string_impl_generics std::str::FromStr for #pneu_string_name #pneu_string_type_generics #pneu_string_where_clause {
-            type Err = <#pneu_str_name #pneu_string_type_generics as pneutype::Validate>::Error;
-            fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-                <#pneu_str_name #pneu_string_type_generics as pneutype::Validate>::validate(s)?;
-                let s = s.to_string();
-                Ok(#self_construction)
-            }
-        }
+        #from_str_maybe
 
-        impl #pneu_string_impl_generics pneutype::PneuString for #pneu_string_name #pneu_string_type_generics #pneu_string_where_clause {
-            type Borrowed = #pneu_str_name #pneu_string_type_generics;
-            type FromStrErr = <Self as std::str::FromStr>::Err;
-            type TryFromStringErr = <Self as TryFrom<String>>::Error;
-            unsafe fn new_unchecked(s: String) -> Self {
-                Self::new_unchecked(s)
-            }
-            fn as_pneu_str(&self) -> &Self::Borrowed {
-                self.#as_pneu_str()
-            }
-            fn into_string(self) -> String {
-                self.into_string()
-            }
-        }
+        #pneu_string_trait
 
         #serde_serialize_maybe
 
         impl #pneu_string_impl_generics std::borrow::ToOwned for #pneu_str_name #pneu_string_type_generics #pneu_string_where_clause {
             type Owned = #pneu_string_name #pneu_string_type_generics;
             fn to_owned(&self) -> Self::Owned {
-                use std::ops::Deref;
-                let s = self.deref().to_owned();
+                let s = <Self as pneutype::AsStr>::as_str(self).to_owned();
                 #pneu_string_construction
             }
         }
 
         impl #pneu_string_impl_generics TryFrom<&str> for #pneu_string_name #pneu_string_type_generics #pneu_string_where_clause {
-            type Error = <#pneu_str_name #pneu_string_type_generics as pneutype::Validate>::Error;
+            type Error = #conversion_error;
             fn try_from(s: &str) -> std::result::Result<Self, Self::Error> {
-                <#pneu_str_name #pneu_string_type_generics as pneutype::Validate>::validate(s)?;
+                #validate_str
                 let s = s.to_string();
                 Ok(#self_construction)
             }
         }
 
         impl #pneu_string_impl_generics TryFrom<String> for #pneu_string_name #pneu_string_type_generics #pneu_string_where_clause {
-            type Error = <#pneu_str_name #pneu_string_type_generics as pneutype::Validate>::Error;
+            type Error = #conversion_error;
             fn try_from(s: String) -> std::result::Result<Self, Self::Error> {
-                <#pneu_str_name #pneu_string_type_generics as pneutype::Validate>::validate(s.as_str())?;
+                #validate_string
                 unsafe { Ok(Self::new_unchecked(s)) }
             }
         }
@@ -319,10 +1137,10 @@ pub fn derive_pneu_string(token_stream: proc_macro::TokenStream) -> proc_macro::
     if DEBUG_OUTPUT {
         let filename = format!("derive_pneu_string.{}.rs", pneu_string_name);
         let mut file = std::fs::File::create(filename.as_str())
-            .expect(format!("Could not create file {:?}", filename).as_str());
+            .unwrap_or_else(|_| panic!("Could not create file {:?}", filename));
         use std::io::Write;
         writeln!(file, "{}", output)
-            .expect(format!("Could not write to file {:?}", filename).as_str());
+            .unwrap_or_else(|_| panic!("Could not write to file {:?}", filename));
         // TODO: Figure out how to run rustfmt on the output.
     }
 
@@ -336,10 +1154,12 @@ pub fn derive_pneu_string(token_stream: proc_macro::TokenStream) -> proc_macro::
 #[derive(FromDeriveInput, Default)]
 #[darling(default, attributes(pneu_str))]
 struct PneuStrArguments {
-    /// Specify true to derive an implementation of serde::Deserialize.  The `serde` crate must be imported into
-    /// the crate in which this PneuStr is defined in order for this to work.  Using this attribute is optional,
-    /// and a manual implementation of serde::Deserialize is of course possible.
-    deserialize: bool,
+    /// Specify `deserialize` (or equivalently `deserialize = "strict"`) to derive an implementation of
+    /// serde::Deserialize.  The `serde` crate must be imported into the crate in which this PneuStr is defined in
+    /// order for this to work.  Using this attribute is optional, and a manual implementation of serde::Deserialize
+    /// is of course possible.  Note that `deserialize = "lenient"` and `deserialize = "normalize"` are only
+    /// supported for PneuString, since canonicalization and normalization may need to allocate.
+    deserialize: DeserializeMode,
     /// Specify true to derive an implementation of serde::Serialize.  The `serde` crate must be imported into
     /// the crate in which this PneuStr is defined in order for this to work.  Using this attribute is optional,
     /// and a manual implementation of serde::Serialize is of course possible.  However, in the case of a PneuStr
@@ -351,6 +1171,81 @@ struct PneuStrArguments {
     /// would be used in the case of a PneuStr having generics, e.g.
     /// `#[derive(pneutype::PneuStr)] #[pneu_str(str_field = "s")] #[repr(transparent)] pub struct ThingStr<T> { t: std::marker::PhantomData<T>, s: str }`
     str_field: Option<String>,
+    /// Optionally specify a human-readable description of what this PneuStr accepts, for use in the error
+    /// messages of the derived implementation of serde::Deserialize, e.g. `expecting = "all-lowercase ASCII"`
+    /// gives `invalid value: string "Foo", expected a LowercaseStr (all-lowercase ASCII)`.  The bare `expecting`
+    /// attribute uses the description given by pneutype::Describe instead (see `constraint`).  If not specified,
+    /// then the validation error is used in its place.
+    expecting: Expecting,
+    /// Optionally define named constants, e.g. `consts(DEFAULT = "default", ADMIN = "admin")` generates
+    /// `pub const DEFAULT: &'static Self` and `pub const ADMIN: &'static Self`.  They're validated at compile time
+    /// (for a PneuStr without generics, even if they're unused), so this requires `validate_const` (unless `one_of`
+    /// is specified).
+    consts: NamedConsts,
+    /// Specify that the PneuStr has an inherent `const fn validate_const(s: &str) -> Result<(), &'static str>` (see
+    /// pneutype::pneu_str!), which is used to validate the consts at compile time.
+    validate_const: bool,
+    /// Optionally name a const (e.g. `default = "DEFAULT"`) to implement std::default::Default for `&Self` with.
+    default: Option<String>,
+    /// Optionally restrict this PneuStr to a fixed set of values, e.g. `one_of("dev", "staging", "prod")`.  This
+    /// generates the impl of pneutype::Validate, `validate_const`, a const for each value (e.g. `DEV`), `all()`,
+    /// and an enum with a variant for each value (e.g. `Dev`), along with conversions to and from it.
+    one_of: Option<OneOf>,
+    /// Optionally specify the name of the enum generated by `one_of`.  If not specified, then it will be the name
+    /// of this PneuStr with any "Str" suffix replaced by "Kind", e.g. "EnvironmentStr" gives "EnvironmentKind".
+    enum_name: Option<String>,
+    /// Optionally specify the parent PneuStr that this PneuStr refines, e.g. `refines = "LowercaseStr"`, which
+    /// requires an impl of pneutype::Refines for it.  This generates the impl of pneutype::Validate, AsRef and From
+    /// for the parent, and TryFrom from the parent.  The `str`-valued field may instead be of the parent type, e.g.
+    /// `pub struct SlugStr(LowercaseStr);`, but `refines` must still be specified.
+    refines: Option<String>,
+    /// Optionally specify the name of an error type to generate, e.g. `error = "InvalidLowercaseStr"`, which is
+    /// then used as the error of the TryFrom<&str> impl instead of the validation error.  It holds the rejected
+    /// `input` and the validation error as `source`, and implements std::error::Error (whose `source()` is the
+    /// validation error if that implements std::error::Error too).
+    error: Option<String>,
+    /// Specify to implement miette::Diagnostic for the error type named by `error` (see the same attribute of
+    /// pneu_string).  The validation error must implement miette::Diagnostic.
+    diagnostic: bool,
+    /// Optionally specify an expression of type pneutype::Constraint describing what this PneuStr accepts, for the
+    /// derived impl of pneutype::Describe, e.g. `constraint = "pneutype::Constraint::Pattern(\"[0-9a-f]+\".into())"`.
+    /// If not specified, then it's given by `one_of` or `expecting` if present, and otherwise is only described by
+    /// the name of this PneuStr.
+    constraint: Option<String>,
+    /// Specify to not derive pneutype::Describe, e.g. in order to implement it manually with different bounds.
+    no_describe: bool,
+    /// Specify to not derive schemars::JsonSchema and utoipa::ToSchema (with the `schemars` and `utoipa` features of
+    /// pneutype), e.g. in order to implement them manually.
+    no_schema: bool,
+    /// Specify `export_ts` to implement pneutype::ExportTs, i.e. export this PneuStr as a branded TypeScript type
+    /// with a type guard, named after this PneuStr, or e.g. `export_ts = "Thingy"` to name it differently (which
+    /// must be a TypeScript identifier).  This generates a test (for a PneuStr without generics) which checks the
+    /// type guard against pneutype::Validate and writes the TypeScript definition to the directory named by the
+    /// PNEUTYPE_EXPORT_TS_DIR environment variable, if it's set.
+    export_ts: Option<ExportTs>,
+    /// Specify to implement Eq, Ord and Hash consistently with those of str (as std::borrow::Borrow<str> requires),
+    /// and PartialEq and PartialOrd between this PneuStr (and &PneuStr) and str, &str, String and Cow<str>, in both
+    /// directions.  These must then not be derived.
+    str_cmp: bool,
+    /// Specify to not implement std::ops::Deref<Target = str>, e.g. so that the str API doesn't leak onto opaque
+    /// IDs.  The str is still available via `as_str`.
+    no_deref: bool,
+    /// Specify to not implement std::fmt::Display, e.g. for secrets.  This PneuStr then doesn't implement
+    /// pneutype::PneuStrExt.
+    no_display: bool,
+    /// Optionally override the visibility of generated inherent methods, e.g. `vis(new_ref_unchecked = "pub(crate)")`.
+    /// These can be any of new_ref, new_ref_collect_errors, new_ref_with, new_ref_unchecked,
+    /// new_ref_unchecked_const and as_str.  Only the inherent methods are restricted, so this can't restrict
+    /// new_ref_unchecked, which remains public as the trait method pneutype::NewRefUnchecked::new_ref_unchecked.
+    #[darling(rename = "vis")]
+    method_vis: MethodVisibilities,
+    /// Optionally specify the `format` of the JSON schema generated with the `schemars` feature of pneutype (and of
+    /// the OpenAPI schema generated with the `utoipa` feature), e.g. `schema_format = "hostname"`.
+    schema_format: Option<String>,
+    /// Optionally specify the `description` of the JSON schema generated with the `schemars` feature of pneutype
+    /// (and of the OpenAPI schema generated with the `utoipa` feature).  If not specified, then it's the
+    /// description given by pneutype::Describe.
+    schema_description: Option<String>,
 }
 
 #[proc_macro_derive(PneuStr, attributes(pneu_str))]
@@ -364,7 +1259,17 @@ pub fn derive_pneu_str(token_stream: proc_macro::TokenStream) -> proc_macro::Tok
     let str_field: syn::Expr =
         syn::parse_str(pneu_str_arguments.str_field.as_deref().unwrap_or("0")).unwrap();
 
-    let serde_deserialize_maybe = if pneu_str_arguments.deserialize {
+    match pneu_str_arguments.deserialize {
+        DeserializeMode::Lenient => panic!(
+            "deserialize = \"lenient\" is only supported for PneuString, since canonicalization may need to allocate"
+        ),
+        DeserializeMode::Normalize => panic!(
+            "deserialize = \"normalize\" is only supported for PneuString, since normalization may need to allocate"
+        ),
+        DeserializeMode::None | DeserializeMode::Strict => {}
+    }
+
+    let serde_deserialize_maybe = if pneu_str_arguments.deserialize != DeserializeMode::None {
         // Create new lifetime parameters 'de and 'a
         let lifetime_a = syn::Lifetime::new("'a", proc_macro2::Span::call_site());
         let lifetime_de = syn::Lifetime::new("'de", proc_macro2::Span::call_site());
@@ -425,6 +1330,20 @@ pub fn derive_pneu_str(token_stream: proc_macro::TokenStream) -> proc_macro::Tok
             _serde_deserialize_visitor_where_clause,
         ) = serde_deserialize_visitor_generics.split_for_impl();
 
+        let describe_type = quote! { #pneu_str_name #pneu_str_type_generics };
+        let serde_expecting = serde_expected(
+            &pneu_str_name,
+            &describe_type,
+            &pneu_str_arguments.expecting,
+            "borrowed ",
+        );
+        let serde_invalid_value_arm_v = serde_invalid_value_arm(
+            &pneu_str_name,
+            &describe_type,
+            &pneu_str_arguments.expecting,
+            quote! { serde::de::Unexpected::Str(v) },
+        );
+
         quote! {
             impl #serde_deserialize_impl_generics serde::Deserialize<#lifetime_de> for &#lifetime_a #pneu_str_name #pneu_str_type_generics #pneu_str_where_clause {
                 fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
@@ -437,13 +1356,28 @@ pub fn derive_pneu_str(token_stream: proc_macro::TokenStream) -> proc_macro::Tok
                         type Value = &#lifetime_a #pneu_str_name #pneu_str_type_generics;
 
                         fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                            formatter.write_str("a borrowed string")
+                            #serde_expecting
                         }
                         fn visit_borrowed_str<E>(self, v: &#lifetime_a str) -> std::result::Result<Self::Value, E>
                         where
                             E: serde::de::Error,
                         {
-                            #pneu_str_name::new_ref(v).map_err(serde::de::Error::custom)
+                            match <#pneu_str_name #pneu_str_type_generics as pneutype::Validate>::validate(v) {
+                                Ok(()) => Ok(unsafe { #pneu_str_name::new_ref_unchecked(v) }),
+                                #serde_invalid_value_arm_v
+                            }
+                        }
+                        fn visit_str<E>(self, v: &str) -> std::result::Result<Self::Value, E>
+                        where
+                            E: serde::de::Error,
+                        {
+                            // The deserializer could not lend out the string (e.g. because it contained escape
+                            // sequences), so it can't be borrowed.  Report the validation error in preference,
+                            // since that is a problem with the data itself.
+                            match <#pneu_str_name #pneu_str_type_generics as pneutype::Validate>::validate(v) {
+                                Ok(()) => Err(serde::de::Error::custom(format_args!("can't borrow {:?} from the input (e.g. because it contains escape sequences); deserialize the owned PneuString instead", v))),
+                                #serde_invalid_value_arm_v
+                            }
                         }
                     }
 
@@ -484,27 +1418,480 @@ pub fn derive_pneu_str(token_stream: proc_macro::TokenStream) -> proc_macro::Tok
     let (try_from_impl_generics, _try_from_type_generics, _try_from_where_clause) =
         try_from_generics.split_for_impl();
 
+    // An unchecked const would only be caught when used, so the consts are always validated at compile time, using
+    // the validate_const that's either provided (and requested via the validate_const attribute) or generated by
+    // one_of.
+    if !pneu_str_arguments.consts.0.is_empty()
+        && !pneu_str_arguments.validate_const
+        && pneu_str_arguments.one_of.is_none()
+    {
+        panic!(
+            "consts requires validate_const, so that the consts can be validated at compile time"
+        );
+    }
+    let named_consts = pneu_str_arguments
+        .consts
+        .0
+        .iter()
+        .map(|(const_name, value)| {
+            let doc = format!("The {} `{:?}`.", pneu_str_name, value);
+            quote! {
+                #[doc = #doc]
+                pub const #const_name: &'static Self = match Self::validate_const(#value) {
+                    // Safe because the validation constraint was just checked.
+                    Ok(()) => unsafe { Self::new_ref_unchecked_const(#value) },
+                    Err(err) => panic!("{}", err),
+                };
+            }
+        })
+        .collect::<Vec<_>>();
+    // A const is only evaluated where it's used, so each one is used here in order for an invalid value to fail the
+    // build even if nothing else uses it.  This can only be done without generics, since otherwise there's no
+    // concrete type to evaluate it for.
+    let named_consts_checks = if input.generics.params.is_empty() {
+        pneu_str_arguments
+            .consts
+            .0
+            .iter()
+            .map(|(const_name, _)| {
+                quote! {
+                    const _: &#pneu_str_name = #pneu_str_name::#const_name;
+                }
+            })
+            .collect::<Vec<_>>()
+    } else {
+        Vec::new()
+    };
+    let default_maybe = match pneu_str_arguments.default.as_deref() {
+        Some(const_name) => {
+            let const_name = syn::Ident::new(const_name, proc_macro2::Span::call_site());
+            quote! {
+                impl #pneu_str_impl_generics std::default::Default for &'static #pneu_str_name #pneu_str_type_generics #pneu_str_where_clause {
+                    fn default() -> Self {
+                        #pneu_str_name::#const_name
+                    }
+                }
+            }
+        }
+        None => quote! {},
+    };
+
+    let parent_type = match pneu_str_arguments.refines.as_deref() {
+        Some(refines) => Some(
+            syn::parse_str::<syn::Type>(refines)
+                .unwrap_or_else(|_| panic!("refines = {:?} is not a type", refines)),
+        ),
+        None => match field_type(
+            &input.data,
+            pneu_str_arguments.str_field.as_deref().unwrap_or("0"),
+        ) {
+            Some(syn::Type::Path(type_path)) if type_path.path.is_ident("str") => None,
+            // Refinement impls are only generated on request, since they make Validate depend on the field's type.
+            Some(field_type) => panic!(
+                "the field of a PneuStr must be str, unless it refines another PneuStr, in which case specify refines = {:?}",
+                quote! { #field_type }.to_string()
+            ),
+            None => panic!(
+                "PneuStr has no field {:?}",
+                pneu_str_arguments.str_field.as_deref().unwrap_or("0")
+            ),
+        },
+    };
+    let refines_maybe = match parent_type.as_ref() {
+        Some(parent_type) => quote! {
+            impl #pneu_str_impl_generics pneutype::Validate for #pneu_str_name #pneu_str_type_generics #pneu_str_where_clause {
+                type Data = str;
+                type Error = <Self as pneutype::Refines<#parent_type>>::Error;
+                fn validate(data: &Self::Data) -> std::result::Result<(), Self::Error> {
+                    <#parent_type as pneutype::Validate>::validate(data)?;
+                    <Self as pneutype::Refines<#parent_type>>::validate_refinement(data)
+                }
+                fn validate_all(data: &Self::Data) -> std::result::Result<(), Vec<Self::Error>> {
+                    // The refinement can only be checked once the data is known to be valid for the parent.
+                    <#parent_type as pneutype::Validate>::validate_all(data)
+                        .map_err(|errs| errs.into_iter().map(Into::into).collect::<Vec<_>>())?;
+                    <Self as pneutype::Refines<#parent_type>>::validate_refinement(data).map_err(|err| vec![err])
+                }
+            }
+
+            impl #pneu_str_impl_generics std::convert::AsRef<#parent_type> for #pneu_str_name #pneu_str_type_generics #pneu_str_where_clause {
+                fn as_ref(&self) -> &#parent_type {
+                    // Safe because every valid value of a refinement is valid for its parent.
+                    unsafe { <#parent_type as pneutype::NewRefUnchecked>::new_ref_unchecked(self.as_str()) }
+                }
+            }
+
+            impl #try_from_impl_generics From<&#try_from_lifetime #pneu_str_name #pneu_str_type_generics> for &#try_from_lifetime #parent_type #pneu_str_where_clause {
+                fn from(s: &#try_from_lifetime #pneu_str_name #pneu_str_type_generics) -> Self {
+                    s.as_ref()
+                }
+            }
+
+            impl #try_from_impl_generics TryFrom<&#try_from_lifetime #parent_type> for &#try_from_lifetime #pneu_str_name #pneu_str_type_generics #pneu_str_where_clause {
+                type Error = <#pneu_str_name #pneu_str_type_generics as pneutype::Validate>::Error;
+                fn try_from(parent: &#try_from_lifetime #parent_type) -> std::result::Result<Self, Self::Error> {
+                    // Only the refinement needs to be checked.
+                    let s = <#parent_type as pneutype::AsStr>::as_str(parent);
+                    <#pneu_str_name #pneu_str_type_generics as pneutype::Refines<#parent_type>>::validate_refinement(s)?;
+                    Ok(unsafe { <#pneu_str_name #pneu_str_type_generics as pneutype::NewRefUnchecked>::new_ref_unchecked(s) })
+                }
+            }
+        },
+        None => quote! {},
+    };
+
+    let one_of_maybe = match pneu_str_arguments.one_of.as_ref() {
+        Some(one_of) => one_of_impls(
+            &input.vis,
+            &pneu_str_name,
+            &input.generics,
+            pneu_str_arguments.enum_name.as_deref(),
+            &one_of.0,
+        ),
+        None => quote! {},
+    };
+
+    if pneu_str_arguments.diagnostic && pneu_str_arguments.error.is_none() {
+        panic!("diagnostic requires error = \"...\"");
+    }
+    let (error_wrapper_maybe, try_from_error, try_from_body) = match pneu_str_arguments
+        .error
+        .as_deref()
+    {
+        Some(error_name) => {
+            if !input.generics.params.is_empty() {
+                panic!("error is not supported for a PneuStr with generics");
+            }
+            let error_name = syn::Ident::new(error_name, proc_macro2::Span::call_site());
+            (
+                error_wrapper(
+                    &input.vis,
+                    &error_name,
+                    &pneu_str_name,
+                    &pneu_str_name,
+                    pneu_str_arguments.diagnostic,
+                ),
+                quote! { #error_name },
+                quote! {
+                    #pneu_str_name::new_ref(s).map_err(|source| #error_name { input: s.to_string(), source })
+                },
+            )
+        }
+        None => (
+            quote! {},
+            quote! { <#pneu_str_name #pneu_str_type_generics as pneutype::Validate>::Error },
+            quote! { #pneu_str_name::new_ref(s) },
+        ),
+    };
+
+    let describe_maybe = if pneu_str_arguments.no_describe {
+        quote! {}
+    } else {
+        let type_name = pneu_str_name.to_string();
+        let describe_expecting = describe_expecting(&pneu_str_arguments.expecting);
+        let constraint_is_custom =
+            pneu_str_arguments.constraint.is_none() && pneu_str_arguments.one_of.is_none();
+        let constraint = match (
+            pneu_str_arguments.constraint.as_deref(),
+            pneu_str_arguments.one_of.as_ref(),
+            &pneu_str_arguments.expecting,
+        ) {
+            (Some(constraint), _, _) => {
+                let constraint = syn::parse_str::<syn::Expr>(constraint).unwrap_or_else(|_| {
+                    panic!("constraint = {:?} is not an expression", constraint)
+                });
+                quote! { #constraint }
+            }
+            (None, Some(one_of), _) => {
+                let values = &one_of.0;
+                quote! { pneutype::Constraint::OneOf(vec![#(std::borrow::Cow::Borrowed(#values)),*]) }
+            }
+            (None, None, Expecting::Literal(expecting)) => quote! {
+                pneutype::Constraint::Custom(std::borrow::Cow::Borrowed(#expecting))
+            },
+            (None, None, _) => {
+                let description = format!("a valid {}", type_name);
+                quote! { pneutype::Constraint::Custom(std::borrow::Cow::Borrowed(#description)) }
+            }
+        };
+        quote! {
+            impl #pneu_str_impl_generics pneutype::Describe for #pneu_str_name #pneu_str_type_generics #pneu_str_where_clause {
+                const TYPE_NAME: &'static str = #type_name;
+                const CONSTRAINT_IS_CUSTOM: bool = #constraint_is_custom;
+                fn constraint() -> pneutype::Constraint {
+                    #constraint
+                }
+                #describe_expecting
+            }
+        }
+    };
+
+    let json_schema_maybe = if cfg!(feature = "schemars") && !pneu_str_arguments.no_schema {
+        let mut json_schema_where_clause = input.generics.clone().make_where_clause().clone();
+        json_schema_where_clause.predicates.push(hrtb_bound(
+            quote! { #pneu_str_name #pneu_str_type_generics },
+            quote! { pneutype::Describe },
+        ));
+        let schema_name = pneu_str_name.to_string();
+        let schema_id = quote! { concat!(module_path!(), "::", #schema_name) };
+        // The schemas of different instantiations of a generic PneuStr differ, but would have the same name.
+        let inline_schema = !input.generics.params.is_empty();
+        let description = match pneu_str_arguments.schema_description.as_deref() {
+            Some(schema_description) => quote! { #schema_description },
+            None => {
+                quote! { <#pneu_str_name #pneu_str_type_generics as pneutype::Describe>::description() }
+            }
+        };
+        let format_maybe = match pneu_str_arguments.schema_format.as_deref() {
+            Some(schema_format) => quote! {
+                schema.insert("format".to_string(), #schema_format.into());
+            },
+            None => quote! {},
+        };
+        quote! {
+            impl #pneu_str_impl_generics pneutype::schemars::JsonSchema for #pneu_str_name #pneu_str_type_generics #json_schema_where_clause {
+                fn inline_schema() -> bool {
+                    #inline_schema
+                }
+                fn schema_name() -> std::borrow::Cow<'static, str> {
+                    std::borrow::Cow::Borrowed(#schema_name)
+                }
+                fn schema_id() -> std::borrow::Cow<'static, str> {
+                    std::borrow::Cow::Borrowed(#schema_id)
+                }
+                fn json_schema(_generator: &mut pneutype::schemars::SchemaGenerator) -> pneutype::schemars::Schema {
+                    let mut schema = <#pneu_str_name #pneu_str_type_generics as pneutype::Describe>::constraint().json_schema();
+                    schema.insert("description".to_string(), #description.into());
+                    #format_maybe
+                    schema
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let openapi_schema_maybe = if cfg!(feature = "utoipa") && !pneu_str_arguments.no_schema {
+        let mut openapi_schema_where_clause = input.generics.clone().make_where_clause().clone();
+        openapi_schema_where_clause.predicates.push(hrtb_bound(
+            quote! { #pneu_str_name #pneu_str_type_generics },
+            quote! { pneutype::Describe },
+        ));
+        // The schemas of different instantiations of a generic PneuStr differ, so they need different names, which
+        // are made of the generic arguments (see pneutype::Describe::generic_type_name).  Lifetimes don't affect the
+        // schema, so they're left out.
+        let name = if input.generics.params.is_empty() {
+            let name = pneu_str_name.to_string();
+            quote! { std::borrow::Cow::Borrowed(#name) }
+        } else {
+            let generic_args = input
+                .generics
+                .params
+                .iter()
+                .filter_map(|generic_param| match generic_param {
+                    syn::GenericParam::Type(type_param) => {
+                        let ident = &type_param.ident;
+                        openapi_schema_where_clause
+                            .predicates
+                            .push(syn::parse_quote! { #ident: pneutype::Describe });
+                        Some(quote! { <#ident as pneutype::Describe>::generic_type_name().into_owned() })
+                    }
+                    syn::GenericParam::Const(const_param) => {
+                        let ident = &const_param.ident;
+                        Some(quote! { #ident.to_string() })
+                    }
+                    syn::GenericParam::Lifetime(_) => None,
+                })
+                .collect::<Vec<_>>();
+            quote! {
+                std::borrow::Cow::Owned(
+                    [
+                        <Self as pneutype::Describe>::TYPE_NAME.to_string(),
+                        #(#generic_args),*
+                    ]
+                    .join("_")
+                )
+            }
+        };
+        let description = match pneu_str_arguments.schema_description.as_deref() {
+            Some(schema_description) => quote! { #schema_description },
+            None => {
+                quote! { <#pneu_str_name #pneu_str_type_generics as pneutype::Describe>::description() }
+            }
+        };
+        let format_maybe = match pneu_str_arguments.schema_format.as_deref() {
+            Some(schema_format) => quote! {
+                schema.format = Some(pneutype::utoipa::openapi::schema::SchemaFormat::Custom(#schema_format.to_string()));
+            },
+            None => quote! {},
+        };
+        quote! {
+            impl #pneu_str_impl_generics pneutype::utoipa::PartialSchema for #pneu_str_name #pneu_str_type_generics #openapi_schema_where_clause {
+                fn schema() -> pneutype::utoipa::openapi::RefOr<pneutype::utoipa::openapi::schema::Schema> {
+                    let mut schema = <#pneu_str_name #pneu_str_type_generics as pneutype::Describe>::constraint().openapi_schema();
+                    schema.description = Some(#description.to_string());
+                    #format_maybe
+                    schema.into()
+                }
+            }
+
+            impl #pneu_str_impl_generics pneutype::utoipa::ToSchema for #pneu_str_name #pneu_str_type_generics #openapi_schema_where_clause {
+                fn name() -> std::borrow::Cow<'static, str> {
+                    #name
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let export_ts_maybe = match pneu_str_arguments.export_ts.as_ref() {
+        Some(export_ts) => {
+            let ts_name = export_ts
+                .0
+                .clone()
+                .unwrap_or_else(|| pneu_str_name.to_string());
+            if !is_ts_identifier(&ts_name) {
+                panic!(
+                    "PneuStr attribute `export_ts` value {:?} is not a valid TypeScript identifier",
+                    ts_name
+                );
+            }
+            // A test can't be generic, so only non-generic PneuStrs are exported at test time.
+            let export_ts_test_maybe = if input.generics.params.is_empty() {
+                let test_name = quote::format_ident!("__pneutype_export_ts_{}", pneu_str_name);
+                quote! {
+                    #[cfg(test)]
+                    #[test]
+                    #[allow(non_snake_case)]
+                    fn #test_name() {
+                        <#pneu_str_name as pneutype::ExportTs>::check_ts_guard();
+                        if let Some(dir) = std::env::var_os(pneutype::EXPORT_TS_DIR_VAR) {
+                            <#pneu_str_name as pneutype::ExportTs>::export_ts_to(dir).expect("Could not export TypeScript definition");
+                        }
+                    }
+                }
+            } else {
+                quote! {}
+            };
+            quote! {
+                impl #pneu_str_impl_generics pneutype::ExportTs for #pneu_str_name #pneu_str_type_generics #pneu_str_where_clause {
+                    const TS_NAME: &'static str = #ts_name;
+                }
+
+                #export_ts_test_maybe
+            }
+        }
+        None => quote! {},
+    };
+
+    let [new_ref_vis, new_ref_collect_errors_vis, new_ref_with_vis, new_ref_unchecked_vis, new_ref_unchecked_const_vis, as_str_vis] =
+        pneu_str_arguments.method_vis.resolve([
+            "new_ref",
+            "new_ref_collect_errors",
+            "new_ref_with",
+            "new_ref_unchecked",
+            "new_ref_unchecked_const",
+            "as_str",
+        ]);
+
+    let deref_maybe = if pneu_str_arguments.no_deref {
+        quote! {}
+    } else {
+        quote! {
+            impl #pneu_str_impl_generics std::ops::Deref for #pneu_str_name #pneu_str_type_generics #pneu_str_where_clause {
+                type Target = str;
+                fn deref(&self) -> &Self::Target {
+                    <Self as pneutype::AsStr>::as_str(self)
+                }
+            }
+        }
+    };
+
+    let display_maybe = if pneu_str_arguments.no_display {
+        quote! {}
+    } else {
+        quote! {
+            impl #pneu_str_impl_generics std::fmt::Display for #pneu_str_name #pneu_str_type_generics #pneu_str_where_clause {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+                    <Self as pneutype::AsStr>::as_str(self).fmt(f)
+                }
+            }
+        }
+    };
+
+    let str_cmp_maybe = if pneu_str_arguments.str_cmp {
+        let pneu_str_type = quote! { #pneu_str_name #pneu_str_type_generics };
+        let others = [
+            quote! { str },
+            quote! { String },
+            quote! { std::borrow::Cow<'__cmp, str> },
+        ];
+        // &PneuStr vs &str is already covered by the std impls for references.
+        let impls = str_cmp_impls(
+            &input.generics,
+            &pneu_str_type,
+            &[&others[..], &[quote! { &'__cmp str }]].concat(),
+        );
+        let ref_impls =
+            str_cmp_cross_impls(&input.generics, &quote! { &'__cmp #pneu_str_type }, &others);
+        quote! {
+            #impls
+
+            #ref_impls
+        }
+    } else {
+        quote! {}
+    };
+
     let output = quote! {
+        #one_of_maybe
+
+        #error_wrapper_maybe
+
+        #export_ts_maybe
+
+        #describe_maybe
+
+        #json_schema_maybe
+
+        #openapi_schema_maybe
+
+        #str_cmp_maybe
+
+        #(#named_consts_checks)*
+
         impl #pneu_str_impl_generics #pneu_str_name #pneu_str_type_generics #pneu_str_where_clause {
+            #(#named_consts)*
             /// Validate the given str and wrap it as a reference to this PneuStr type.
-            pub fn new_ref(s: &str) -> std::result::Result<&Self, <Self as pneutype::Validate>::Error> where Self: pneutype::Validate<Data = str> {
+            #new_ref_vis fn new_ref(s: &str) -> std::result::Result<&Self, <Self as pneutype::Validate>::Error> where Self: pneutype::Validate<Data = str> {
                 <Self as pneutype::PneuStr>::new_ref(s)
             }
+            /// Validate the given str, reporting every failure instead of just the first (see
+            /// pneutype::Validate::validate_all), and wrap it as a reference to this PneuStr type.
+            #new_ref_collect_errors_vis fn new_ref_collect_errors(s: &str) -> std::result::Result<&Self, Vec<<Self as pneutype::Validate>::Error>> where Self: pneutype::Validate<Data = str> {
+                <Self as pneutype::PneuStr>::new_ref_collect_errors(s)
+            }
+            /// Validate the given str, including against the given runtime context (see pneutype::ValidateWith),
+            /// and wrap it as a reference to this PneuStr type.
+            #new_ref_with_vis fn new_ref_with<'s, Ctx: ?Sized>(s: &'s str, ctx: &Ctx) -> std::result::Result<&'s Self, <Self as pneutype::Validate>::Error> where Self: pneutype::Validate<Data = str> + pneutype::ValidateWith<Ctx> {
+                <Self as pneutype::PneuStr>::new_ref_with(s, ctx)
+            }
             /// Unsafe: Wrap the given str as a reference to this PneuStr type without validating it.
             /// This requires the caller to guarantee validity.  However, a debug_assert! will be used
             /// to check the validity condition.  For a const version of this, see new_ref_unchecked_const.
-            pub unsafe fn new_ref_unchecked(s: &str) -> &Self {
+            #new_ref_unchecked_vis unsafe fn new_ref_unchecked(s: &str) -> &Self {
                 <Self as pneutype::NewRefUnchecked>::new_ref_unchecked(s)
             }
             /// Unsafe: Wrap the given str as a reference to this PneuStr type without validating it.
             /// This requires the caller to guarantee validity.  Because this is a const function, the
             /// validity condition can't be checked in a debug_assert! as it is in new_ref_unchecked.
-            pub const unsafe fn new_ref_unchecked_const(s: &str) -> &Self {
+            #new_ref_unchecked_const_vis const unsafe fn new_ref_unchecked_const(s: &str) -> &Self {
                 // See https://stackoverflow.com/questions/64977525/how-can-i-create-newtypes-for-an-unsized-type-and-its-owned-counterpart-like-s
                 &*(s as *const str as *const Self)
             }
             /// Return the raw &str underlying this PneuStr.
-            pub fn as_str(&self) -> &str {
+            #as_str_vis fn as_str(&self) -> &str {
                 <Self as pneutype::AsStr>::as_str(self)
             }
         }
@@ -527,20 +1914,11 @@ pub fn derive_pneu_str(token_stream: proc_macro::TokenStream) -> proc_macro::Tok
             }
         }
 
-        impl #pneu_str_impl_generics std::ops::Deref for #pneu_str_name #pneu_str_type_generics #pneu_str_where_clause {
-            type Target = str;
-            fn deref(&self) -> &Self::Target {
-                Self::as_str(self)
-            }
-        }
+        #deref_maybe
 
         #serde_deserialize_maybe
 
-        impl #pneu_str_impl_generics std::fmt::Display for #pneu_str_name #pneu_str_type_generics #pneu_str_where_clause {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
-                Self::as_str(self).fmt(f)
-            }
-        }
+        #display_maybe
 
         impl #pneu_str_impl_generics pneutype::NewRefUnchecked for #pneu_str_name #pneu_str_type_generics #pneu_str_where_clause {
             type Input = str;
@@ -554,11 +1932,16 @@ pub fn derive_pneu_str(token_stream: proc_macro::TokenStream) -> proc_macro::Tok
         #serde_serialize_maybe
 
         impl #try_from_impl_generics TryFrom<&#try_from_lifetime str> for &#try_from_lifetime #pneu_str_name #pneu_str_type_generics #pneu_str_where_clause {
-            type Error = <#pneu_str_name #pneu_str_type_generics as pneutype::Validate>::Error;
+            type Error = #try_from_error;
             fn try_from(s: &#try_from_lifetime str) -> std::result::Result<Self, Self::Error> {
-                #pneu_str_name::new_ref(s)
+                #try_from_body
             }
         }
+
+        #default_maybe
+
+        #refines_maybe
+
     };
 
     // NOTE: This is for debugging the output of the proc macro.  `cargo expand` doesn't seem to actually capture
@@ -569,12 +1952,468 @@ pub fn derive_pneu_str(token_stream: proc_macro::TokenStream) -> proc_macro::Tok
     if DEBUG_OUTPUT {
         let filename = format!("derive_pneu_str.{}.rs", pneu_str_name);
         let mut file = std::fs::File::create(filename.as_str())
-            .expect(format!("Could not create file {:?}", filename).as_str());
+            .unwrap_or_else(|_| panic!("Could not create file {:?}", filename));
         use std::io::Write;
         writeln!(file, "{}", output)
-            .expect(format!("Could not write to file {:?}", filename).as_str());
+            .unwrap_or_else(|_| panic!("Could not write to file {:?}", filename));
         // TODO: Figure out how to run rustfmt on the output.
     }
 
     output.into()
 }
+
+//
+// Helpers
+//
+
+/// Returns the body of `serde::de::Visitor::expecting`, which writes e.g. "a LowercaseStr (all-lowercase ASCII)",
+/// where `qualifier` is prepended to the type name, and `describe_type` is the type whose pneutype::Describe impl
+/// is used for the bare `expecting` attribute.
+fn serde_expected(
+    type_name: &syn::Ident,
+    describe_type: &proc_macro2::TokenStream,
+    expecting: &Expecting,
+    qualifier: &str,
+) -> proc_macro2::TokenStream {
+    match expecting {
+        Expecting::Literal(expecting) => {
+            let expected = format!("a {}{} ({})", qualifier, type_name, expecting);
+            quote! { formatter.write_str(#expected) }
+        }
+        Expecting::Describe => {
+            let expected_format = format!("a {}{} ({{}})", qualifier, type_name);
+            quote! { write!(formatter, #expected_format, <#describe_type as pneutype::Describe>::description()) }
+        }
+        Expecting::None => {
+            let expected = format!("a {}{}", qualifier, type_name);
+            quote! { formatter.write_str(#expected) }
+        }
+    }
+}
+
+/// Returns the where predicate `for<'__pneutype> #ty: #bounds`.  When `ty` isn't generic, the plain `#ty: #bounds`
+/// would be a trivial bound, which is an error if it doesn't hold, whereas the higher-ranked one just makes the impl
+/// or method that it's on unavailable.  So this is how generated items are made conditional on e.g. an optional
+/// trait of the PneuStr.
+fn hrtb_bound(
+    ty: proc_macro2::TokenStream,
+    bounds: proc_macro2::TokenStream,
+) -> syn::WherePredicate {
+    syn::parse_quote! { for<'__pneutype> #ty: #bounds }
+}
+
+/// Returns the pneutype::Describe::expecting method for the given `expecting` attribute, if it overrides the default.
+fn describe_expecting(expecting: &Expecting) -> proc_macro2::TokenStream {
+    match expecting {
+        Expecting::Literal(expecting) => quote! {
+            fn expecting() -> Option<std::borrow::Cow<'static, str>> {
+                Some(std::borrow::Cow::Borrowed(#expecting))
+            }
+        },
+        Expecting::Describe => quote! {
+            fn expecting() -> Option<std::borrow::Cow<'static, str>> {
+                Some(<Self as pneutype::Describe>::description())
+            }
+        },
+        Expecting::None => quote! {},
+    }
+}
+
+/// Returns the match arm for the `Err` case of `pneutype::Validate::validate` in a generated serde visitor, which
+/// reports the `serde::de::Unexpected`-valued expression `unexpected` via `serde::de::Error::invalid_value`.  If no `expecting` description was
+/// given, then the validation error is used in its place.
+fn serde_invalid_value_arm(
+    type_name: &syn::Ident,
+    describe_type: &proc_macro2::TokenStream,
+    expecting: &Expecting,
+    unexpected: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let expected_format = format!("a {} ({{}})", type_name);
+    match expecting {
+        Expecting::Literal(expecting) => {
+            let expected = format!("a {} ({})", type_name, expecting);
+            quote! {
+                Err(_) => Err(serde::de::Error::invalid_value(#unexpected, &#expected)),
+            }
+        }
+        Expecting::Describe => quote! {
+            Err(_) => Err(serde::de::Error::invalid_value(#unexpected, &format!(#expected_format, <#describe_type as pneutype::Describe>::description()).as_str())),
+        },
+        Expecting::None => quote! {
+            Err(err) => Err(serde::de::Error::invalid_value(#unexpected, &format!(#expected_format, err).as_str())),
+        },
+    }
+}
+
+/// Generates the impls of the `str_cmp` attribute for the pneutype `self_type` with the given generics, i.e. Eq, Ord
+/// and Hash consistent with those of str, and PartialEq and PartialOrd with each of the `others` types (see
+/// [str_cmp_cross_impls]).  Comparisons all go through AsRef<str>.
+fn str_cmp_impls(
+    generics: &syn::Generics,
+    self_type: &proc_macro2::TokenStream,
+    others: &[proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    let (impl_generics, _type_generics, where_clause) = generics.split_for_impl();
+    let cross_impls = str_cmp_cross_impls(generics, self_type, others);
+    quote! {
+        impl #impl_generics PartialEq for #self_type #where_clause {
+            fn eq(&self, other: &Self) -> bool {
+                <Self as AsRef<str>>::as_ref(self) == <Self as AsRef<str>>::as_ref(other)
+            }
+        }
+
+        impl #impl_generics Eq for #self_type #where_clause {}
+
+        impl #impl_generics PartialOrd for #self_type #where_clause {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl #impl_generics Ord for #self_type #where_clause {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                <Self as AsRef<str>>::as_ref(self).cmp(<Self as AsRef<str>>::as_ref(other))
+            }
+        }
+
+        impl #impl_generics std::hash::Hash for #self_type #where_clause {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                <Self as AsRef<str>>::as_ref(self).hash(state)
+            }
+        }
+
+        #cross_impls
+    }
+}
+
+/// Generates PartialEq and PartialOrd between `self_type` and each of the `others` types, in both directions, where
+/// the lifetime `'__cmp` may be used in the types.
+fn str_cmp_cross_impls(
+    generics: &syn::Generics,
+    self_type: &proc_macro2::TokenStream,
+    others: &[proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    let mut cmp_generics = generics.clone();
+    cmp_generics.params.insert(
+        0,
+        syn::GenericParam::Lifetime(syn::LifetimeDef::new(syn::Lifetime::new(
+            "'__cmp",
+            proc_macro2::Span::call_site(),
+        ))),
+    );
+    let (impl_generics, _type_generics, where_clause) = cmp_generics.split_for_impl();
+    let impls = others.iter().flat_map(|other| {
+        [(self_type, other), (other, self_type)].map(|(lhs, rhs)| {
+            quote! {
+                impl #impl_generics PartialEq<#rhs> for #lhs #where_clause {
+                    fn eq(&self, other: &#rhs) -> bool {
+                        <#lhs as AsRef<str>>::as_ref(self) == <#rhs as AsRef<str>>::as_ref(other)
+                    }
+                }
+
+                impl #impl_generics PartialOrd<#rhs> for #lhs #where_clause {
+                    fn partial_cmp(&self, other: &#rhs) -> Option<std::cmp::Ordering> {
+                        <#lhs as AsRef<str>>::as_ref(self).partial_cmp(<#rhs as AsRef<str>>::as_ref(other))
+                    }
+                }
+            }
+        })
+    });
+    quote! { #(#impls)* }
+}
+
+/// Generates the error type named by the `error` attribute, which wraps the validation error of the (non-generic)
+/// PneuStr along with the rejected input, and names the given type in its Display impl.
+fn error_wrapper(
+    vis: &syn::Visibility,
+    error_name: &syn::Ident,
+    type_name: &syn::Ident,
+    pneu_str_name: &syn::Ident,
+    diagnostic: bool,
+) -> proc_macro2::TokenStream {
+    let doc = format!("The error for a rejected conversion into {}.", type_name);
+    let diagnostic_maybe = if diagnostic {
+        quote! {
+            // This names the requirement in the compile error, rather than leaving it to the forwarding methods.
+            const _: fn() = {
+                fn validation_error_must_implement_miette_diagnostic<T: pneutype::miette::Diagnostic>() {}
+                validation_error_must_implement_miette_diagnostic::<<#pneu_str_name as pneutype::Validate>::Error>
+            };
+
+            impl pneutype::miette::Diagnostic for #error_name {
+                fn code<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+                    self.source.code()
+                }
+                fn severity(&self) -> Option<pneutype::miette::Severity> {
+                    self.source.severity()
+                }
+                fn help<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+                    self.source.help()
+                }
+                fn url<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+                    self.source.url()
+                }
+                fn source_code(&self) -> Option<&dyn pneutype::miette::SourceCode> {
+                    Some(&self.input)
+                }
+                fn labels(&self) -> Option<Box<dyn Iterator<Item = pneutype::miette::LabeledSpan> + '_>> {
+                    self.source.labels()
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let display_format = format!("invalid {} {{:?}}: {{}}", type_name);
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug)]
+        #vis struct #error_name {
+            /// The rejected input.
+            pub input: String,
+            /// The validation error.
+            pub source: <#pneu_str_name as pneutype::Validate>::Error,
+        }
+
+        impl std::fmt::Display for #error_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+                write!(f, #display_format, self.input, self.source)
+            }
+        }
+
+        impl std::error::Error for #error_name {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                // The source is only reported if the validation error implements std::error::Error.
+                use pneutype::{ErrorSourceViaError as _, ErrorSourceViaOther as _};
+                (&pneutype::ErrorSource(&self.source)).error_source()
+            }
+        }
+
+        #diagnostic_maybe
+    }
+}
+
+/// Returns the type of the given field of a struct, where `field` is either the field name or its index.
+fn field_type<'a>(data: &'a syn::Data, field: &str) -> Option<&'a syn::Type> {
+    let fields = match data {
+        syn::Data::Struct(data_struct) => &data_struct.fields,
+        _ => return None,
+    };
+    fields.iter().enumerate().find_map(|(i, f)| {
+        let matches = match f.ident.as_ref() {
+            Some(ident) => ident == field,
+            None => i.to_string() == field,
+        };
+        matches.then_some(&f.ty)
+    })
+}
+
+/// Returns the identifier for a `one_of` value, with words delimited by non-alphanumeric chars, e.g. "us-east-1"
+/// gives "UsEast1" with `upper_camel_case` and "US_EAST_1" without.
+fn one_of_ident(value: &str, upper_camel_case: bool) -> syn::Ident {
+    let words = value
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>();
+    if words.is_empty() {
+        panic!(
+            "one_of value {:?} has no ASCII-alphanumeric chars to derive a name from",
+            value
+        );
+    }
+    let mut ident = if upper_camel_case {
+        words
+            .iter()
+            .map(|word| word[..1].to_ascii_uppercase() + &word[1..].to_ascii_lowercase())
+            .collect::<String>()
+    } else {
+        words
+            .iter()
+            .map(|word| word.to_ascii_uppercase())
+            .collect::<Vec<_>>()
+            .join("_")
+    };
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    // Parsing rejects keywords, e.g. "self" gives "Self".
+    syn::parse_str::<syn::Ident>(&ident).unwrap_or_else(|_| {
+        panic!(
+            "one_of value {:?} gives the name {}, which is a keyword",
+            value, ident
+        )
+    })
+}
+
+/// Panics if two `one_of` values give the same name, e.g. "foo-bar" and "foo_bar", or "Dev" and "dev".
+fn check_one_of_idents_distinct(values: &[String], idents: &[syn::Ident]) {
+    for (i, ident) in idents.iter().enumerate() {
+        if let Some(j) = idents[..i].iter().position(|other| other == ident) {
+            panic!(
+                "one_of values {:?} and {:?} both give the name {}",
+                values[j], values[i], ident
+            );
+        }
+    }
+}
+
+/// Generates everything for the `one_of` attribute of `pneu_str`: the impl of pneutype::Validate, the inherent
+/// `validate_const`, consts and `all()`, and the enum along with its conversions.
+fn one_of_impls(
+    vis: &syn::Visibility,
+    pneu_str_name: &syn::Ident,
+    generics: &syn::Generics,
+    enum_name: Option<&str>,
+    values: &[String],
+) -> proc_macro2::TokenStream {
+    if !generics.params.is_empty() {
+        panic!("one_of is not supported for a PneuStr with generics");
+    }
+    if values.is_empty() {
+        panic!("one_of must specify at least one value");
+    }
+    for (i, value) in values.iter().enumerate() {
+        if values[..i].contains(value) {
+            panic!("one_of value {:?} is repeated", value);
+        }
+    }
+
+    let enum_name = match enum_name {
+        Some(enum_name) => syn::Ident::new(enum_name, proc_macro2::Span::call_site()),
+        None => {
+            let pneu_str_name = pneu_str_name.to_string();
+            let base_name = pneu_str_name.strip_suffix("Str").unwrap_or(&pneu_str_name);
+            quote::format_ident!("{}Kind", base_name)
+        }
+    };
+    let const_names = values
+        .iter()
+        .map(|value| one_of_ident(value, false))
+        .collect::<Vec<_>>();
+    let variant_names = values
+        .iter()
+        .map(|value| one_of_ident(value, true))
+        .collect::<Vec<_>>();
+    check_one_of_idents_distinct(values, &const_names);
+    check_one_of_idents_distinct(values, &variant_names);
+    let value_count = values.len();
+    let error_message = format!(
+        "{} must be one of {}",
+        pneu_str_name,
+        values
+            .iter()
+            .map(|value| format!("{:?}", value))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    let enum_doc = format!("The values of {}, for exhaustive matching.", pneu_str_name);
+    let const_docs = values
+        .iter()
+        .map(|value| format!("The {} `{:?}`.", pneu_str_name, value))
+        .collect::<Vec<_>>();
+
+    quote! {
+        impl pneutype::Validate for #pneu_str_name {
+            type Data = str;
+            type Error = &'static str;
+            fn validate(data: &Self::Data) -> std::result::Result<(), Self::Error> {
+                match data {
+                    #(#values)|* => Ok(()),
+                    _ => Err(#error_message),
+                }
+            }
+        }
+
+        impl #pneu_str_name {
+            #(
+                #[doc = #const_docs]
+                pub const #const_names: &'static Self = unsafe { Self::new_ref_unchecked_const(#values) };
+            )*
+            /// The const version of the validation constraint, e.g. for use in pneutype::pneu_str!.
+            pub const fn validate_const(s: &str) -> std::result::Result<(), &'static str> {
+                const fn eq(a: &[u8], b: &[u8]) -> bool {
+                    if a.len() != b.len() {
+                        return false;
+                    }
+                    let mut i = 0;
+                    while i < a.len() {
+                        if a[i] != b[i] {
+                            return false;
+                        }
+                        i += 1;
+                    }
+                    true
+                }
+                #(
+                    if eq(s.as_bytes(), #values.as_bytes()) {
+                        return Ok(());
+                    }
+                )*
+                Err(#error_message)
+            }
+            /// Iterates over all values, in the order they were declared.
+            pub fn all() -> impl Iterator<Item = &'static Self> {
+                const ALL: [&#pneu_str_name; #value_count] = [#(#pneu_str_name::#const_names),*];
+                ALL.into_iter()
+            }
+            /// Return the enum variant corresponding to this value.
+            pub fn kind(&self) -> #enum_name {
+                match self.as_str() {
+                    #(#values => #enum_name::#variant_names,)*
+                    _ => unreachable!("programmer error: {} was constructed with an invalid value", stringify!(#pneu_str_name)),
+                }
+            }
+        }
+
+        #[doc = #enum_doc]
+        #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        #vis enum #enum_name {
+            #(
+                #[doc = #const_docs]
+                #variant_names,
+            )*
+        }
+
+        impl #enum_name {
+            /// All variants, in the order they were declared.
+            pub const ALL: [Self; #value_count] = [#(Self::#variant_names),*];
+            /// Return the value corresponding to this variant.
+            pub const fn as_pneu_str(self) -> &'static #pneu_str_name {
+                match self {
+                    #(Self::#variant_names => #pneu_str_name::#const_names,)*
+                }
+            }
+            /// Return the raw &str corresponding to this variant.
+            pub const fn as_str(self) -> &'static str {
+                match self {
+                    #(Self::#variant_names => #values,)*
+                }
+            }
+        }
+
+        impl std::fmt::Display for #enum_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+                self.as_str().fmt(f)
+            }
+        }
+
+        impl From<#enum_name> for &'static #pneu_str_name {
+            fn from(kind: #enum_name) -> Self {
+                kind.as_pneu_str()
+            }
+        }
+
+        impl From<&#pneu_str_name> for #enum_name {
+            fn from(s: &#pneu_str_name) -> Self {
+                s.kind()
+            }
+        }
+
+        impl std::str::FromStr for #enum_name {
+            type Err = &'static str;
+            fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+                Ok(#pneu_str_name::new_ref(s)?.kind())
+            }
+        }
+    }
+}
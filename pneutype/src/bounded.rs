@@ -0,0 +1,49 @@
+#[cfg(feature = "unicode-segmentation")]
+use crate::validators::Graphemes;
+use crate::{
+    validators::{Bounded, Bytes, Chars, LengthError, LengthUnit},
+    Validate, ValidatedStr, ValidatedString,
+};
+
+/// A [PneuStr](crate::PneuStr) whose length in chars is in the range `MIN..=MAX`, e.g. `BoundedStr<1, 255>` for a
+/// typical DB column.
+pub type BoundedStr<const MIN: usize, const MAX: usize> = ValidatedStr<Bounded<Chars, MIN, MAX>>;
+/// The owned counterpart of [BoundedStr].
+pub type BoundedString<const MIN: usize, const MAX: usize> =
+    ValidatedString<Bounded<Chars, MIN, MAX>>;
+/// A [PneuStr](crate::PneuStr) whose length in bytes is in the range `MIN..=MAX`.
+pub type BoundedBytesStr<const MIN: usize, const MAX: usize> =
+    ValidatedStr<Bounded<Bytes, MIN, MAX>>;
+/// The owned counterpart of [BoundedBytesStr].
+pub type BoundedBytesString<const MIN: usize, const MAX: usize> =
+    ValidatedString<Bounded<Bytes, MIN, MAX>>;
+/// A [PneuStr](crate::PneuStr) whose length in grapheme clusters is in the range `MIN..=MAX`.  Requires the
+/// `unicode-segmentation` feature.
+#[cfg(feature = "unicode-segmentation")]
+pub type BoundedGraphemesStr<const MIN: usize, const MAX: usize> =
+    ValidatedStr<Bounded<Graphemes, MIN, MAX>>;
+/// The owned counterpart of [BoundedGraphemesStr].  Requires the `unicode-segmentation` feature.
+#[cfg(feature = "unicode-segmentation")]
+pub type BoundedGraphemesString<const MIN: usize, const MAX: usize> =
+    ValidatedString<Bounded<Graphemes, MIN, MAX>>;
+
+impl<U: LengthUnit, const MIN: usize, const MAX: usize> ValidatedStr<Bounded<U, MIN, MAX>> {
+    /// Wrap the longest prefix of the given str that isn't too long.  This only fails if the str is too short.
+    pub fn truncate_to_fit(s: &str) -> Result<&Self, LengthError> {
+        let s = U::truncate(s, MAX);
+        <Bounded<U, MIN, MAX> as Validate>::validate(s)?;
+        Ok(unsafe { Self::new_ref_unchecked(s) })
+    }
+}
+
+impl<U: LengthUnit, const MIN: usize, const MAX: usize> ValidatedString<Bounded<U, MIN, MAX>> {
+    /// Construct this from the longest prefix of the given string that isn't too long.  This only fails if the
+    /// string is too short.
+    pub fn truncate_to_fit(s: impl AsRef<str> + Into<String>) -> Result<Self, LengthError> {
+        let end = U::truncate(s.as_ref(), MAX).len();
+        let mut s = s.into();
+        s.truncate(end);
+        <Bounded<U, MIN, MAX> as Validate>::validate(s.as_str())?;
+        Ok(unsafe { Self::new_unchecked(s) })
+    }
+}
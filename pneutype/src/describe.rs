@@ -0,0 +1,221 @@
+use std::borrow::Cow;
+
+/// Self-description of what a pneutype (or validator) accepts, so that e.g. serde error messages, schema generation,
+/// CLI help, and error messages can all draw from one source.  This is implemented by the [PneuStr](crate::PneuStr)
+/// and [PneuString](crate::PneuString) derives (see their `constraint` and `no_describe` attributes), and by the
+/// validator marker types in [validators](crate::validators), whose [Constraint]s are machine-readable.
+/// ```
+/// use pneutype::{
+///     validators::{And, AsciiLowercase, CharsAll, MinLen},
+///     Constraint, Describe, ValidatedStr,
+/// };
+///
+/// type UsernameStr = ValidatedStr<And<MinLen<3>, CharsAll<AsciiLowercase>>>;
+/// assert_eq!(
+///     UsernameStr::constraint(),
+///     Constraint::AllOf(vec![
+///         Constraint::Length { unit: "bytes", min: Some(3), max: None },
+///         Constraint::Chars {
+///             description: "ASCII lowercase".into(),
+///             regex_class: Some("a-z".into()),
+///         },
+///     ])
+/// );
+/// assert_eq!(UsernameStr::description(), "at least 3 bytes and only ASCII lowercase chars");
+/// ```
+pub trait Describe {
+    /// The name of the type, e.g. "LowercaseStr".
+    const TYPE_NAME: &'static str;
+    /// Whether the [constraint](Describe::constraint) is known at compile time to be a [Constraint::Custom], which
+    /// values can't be generated from, e.g. for a [PneuStr](crate::PneuStr) derived without a `constraint` or
+    /// `one_of` attribute.  The `Arbitrary` impls generated by the [PneuString](crate::PneuString) derive then fail
+    /// to build unless a seed is given.  By default this is false.
+    const CONSTRAINT_IS_CUSTOM: bool = false;
+    /// The machine-readable constraint tree.
+    fn constraint() -> Constraint;
+    /// The name of the type along with its generic arguments, joined by `_`, e.g. "Bounded_chars_1_8", for telling
+    /// apart the instantiations of a generic type (e.g. in OpenAPI schema names).  Unlike
+    /// [std::any::type_name], this doesn't depend on the compiler version.  By default this is
+    /// [TYPE_NAME](Describe::TYPE_NAME), which is right for a type without generics.
+    fn generic_type_name() -> Cow<'static, str> {
+        Cow::Borrowed(Self::TYPE_NAME)
+    }
+    /// Human-readable description of the constraint, e.g. "at most 8 chars".  By default this is the [Display]
+    /// of the [constraint](Describe::constraint).
+    ///
+    /// [Display]: std::fmt::Display
+    fn description() -> Cow<'static, str> {
+        Cow::Owned(Self::constraint().to_string())
+    }
+    /// What's expected as given by the `expecting` attribute of the derives, which error messages (e.g. those of
+    /// `pneutype::serde` with the `serde` feature) use in place of the validation error if present.  By
+    /// default there's none.
+    fn expecting() -> Option<Cow<'static, str>> {
+        None
+    }
+}
+
+/// A machine-readable description of a validation constraint, as given by [Describe::constraint].  Its
+/// [Display](std::fmt::Display) is a human-readable description.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Constraint {
+    /// Any string is accepted.
+    Unconstrained,
+    /// A constraint that's only described in prose, e.g. "all-lowercase ASCII".
+    Custom(Cow<'static, str>),
+    /// The length, measured in `unit` (e.g. "bytes", "chars" or "graphemes"), is within the given (inclusive)
+    /// bounds.
+    Length {
+        unit: &'static str,
+        min: Option<usize>,
+        max: Option<usize>,
+    },
+    /// Every char is in the described class, e.g. "ASCII lowercase", which is optionally also given as the
+    /// contents of a regex bracket expression, e.g. "a-z".
+    Chars {
+        description: Cow<'static, str>,
+        regex_class: Option<Cow<'static, str>>,
+    },
+    /// The whole string matches the given regular expression.
+    Pattern(Cow<'static, str>),
+    /// The string is one of the given values.
+    OneOf(Vec<Cow<'static, str>>),
+    /// All of the given constraints are satisfied.
+    AllOf(Vec<Constraint>),
+    /// At least one of the given constraints is satisfied.
+    AnyOf(Vec<Constraint>),
+    /// The given constraint is not satisfied.
+    Not(Box<Constraint>),
+}
+
+impl Constraint {
+    /// Returns the bounds on the length in the given unit (e.g. "chars") implied by this constraint, i.e. the
+    /// intersection of the [Constraint::Length]s that must all be satisfied.
+    pub fn length_bounds(&self, unit: &str) -> (Option<usize>, Option<usize>) {
+        match self {
+            Self::Length {
+                unit: length_unit,
+                min,
+                max,
+            } if *length_unit == unit => (*min, *max),
+            Self::AllOf(constraints) => {
+                constraints
+                    .iter()
+                    .fold((None, None), |(min_acc, max_acc), constraint| {
+                        let (min, max) = constraint.length_bounds(unit);
+                        (min_acc.max(min), max_acc.into_iter().chain(max).min())
+                    })
+            }
+            _ => (None, None),
+        }
+    }
+    /// Writes the constraint, parenthesized if it's a compound one.
+    fn fmt_operand(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AllOf(_) | Self::AnyOf(_) => write!(f, "({})", self),
+            _ => write!(f, "{}", self),
+        }
+    }
+}
+
+impl std::fmt::Display for Constraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unconstrained => f.write_str("any string"),
+            Self::Custom(description) => f.write_str(description),
+            Self::Length { unit, min, max } => match (min, max) {
+                (Some(min), Some(max)) if min == max => write!(f, "exactly {} {}", min, unit),
+                (Some(min), Some(max)) => write!(f, "{} to {} {}", min, max, unit),
+                (Some(min), None) => write!(f, "at least {} {}", min, unit),
+                (None, Some(max)) => write!(f, "at most {} {}", max, unit),
+                (None, None) => write!(f, "any number of {}", unit),
+            },
+            Self::Chars { description, .. } => write!(f, "only {} chars", description),
+            Self::Pattern(pattern) => write!(f, "matching /{}/", pattern),
+            Self::OneOf(values) => {
+                f.write_str("one of ")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{:?}", value)?;
+                }
+                Ok(())
+            }
+            Self::AllOf(constraints) | Self::AnyOf(constraints) => {
+                let separator = if matches!(self, Self::AllOf(_)) {
+                    " and "
+                } else {
+                    " or "
+                };
+                for (i, constraint) in constraints.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(separator)?;
+                    }
+                    constraint.fmt_operand(f)?;
+                }
+                Ok(())
+            }
+            Self::Not(constraint) => {
+                f.write_str("not ")?;
+                constraint.fmt_operand(f)
+            }
+        }
+    }
+}
+
+/// Returns the given text as an identifier-like name, with every run of non-alphanumeric chars replaced by `_`,
+/// e.g. "ASCII lowercase" gives "ASCII_lowercase".
+pub(crate) fn identifier(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Parses the contents of a regex bracket expression (as given by [Constraint::Chars]) into inclusive char ranges,
+/// or returns None if it uses anything other than chars, ranges and escapes (e.g. `\d` or negation).
+pub(crate) fn class_ranges(regex_class: &str) -> Option<Vec<(char, char)>> {
+    if regex_class.starts_with('^') {
+        return None;
+    }
+    let mut chars = regex_class.chars().peekable();
+    let mut ranges = Vec::new();
+    while chars.peek().is_some() {
+        let first = class_char(&mut chars)?;
+        let mut last = first;
+        if chars.peek() == Some(&'-') {
+            chars.next();
+            if chars.peek().is_some() {
+                last = class_char(&mut chars)?;
+            } else {
+                // A trailing '-' is literal.
+                ranges.push(('-', '-'));
+            }
+        }
+        if last < first {
+            return None;
+        }
+        ranges.push((first, last));
+    }
+    (!ranges.is_empty()).then_some(ranges)
+}
+
+/// Parses one (possibly escaped) char of a regex bracket expression.
+fn class_char(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> Option<char> {
+    match chars.next()? {
+        '\\' => match chars.next()? {
+            'x' => {
+                let hex = chars.by_ref().take(2).collect::<String>();
+                char::from_u32(u32::from_str_radix(&hex, 16).ok()?)
+            }
+            'n' => Some('\n'),
+            'r' => Some('\r'),
+            't' => Some('\t'),
+            // Other escaped letters and digits are classes (e.g. `\d`) or unsupported.
+            c if c.is_ascii_alphanumeric() => None,
+            c => Some(c),
+        },
+        c => Some(c),
+    }
+}
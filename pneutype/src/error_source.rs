@@ -0,0 +1,28 @@
+/// Wraps the validation error held by the error type generated by the `error` attribute of the derives, so that its
+/// `std::error::Error::source` can return the validation error if (and only if) that implements
+/// [std::error::Error].  The generated code calls `(&ErrorSource(&self.source)).error_source()` with both
+/// [ErrorSourceViaError] and [ErrorSourceViaOther] in scope, and method resolution picks the former (which needs no
+/// extra autoref) whenever it applies.  This works because the validation error is a concrete type there.
+pub struct ErrorSource<'a, E>(pub &'a E);
+
+/// Applies when the validation error implements [std::error::Error].
+pub trait ErrorSourceViaError<'a> {
+    fn error_source(&self) -> Option<&'a (dyn std::error::Error + 'static)>;
+}
+
+impl<'a, E: std::error::Error + 'static> ErrorSourceViaError<'a> for ErrorSource<'a, E> {
+    fn error_source(&self) -> Option<&'a (dyn std::error::Error + 'static)> {
+        Some(self.0)
+    }
+}
+
+/// Applies otherwise, e.g. when the validation error is a `&'static str`.
+pub trait ErrorSourceViaOther<'a> {
+    fn error_source(&self) -> Option<&'a (dyn std::error::Error + 'static)>;
+}
+
+impl<'a, E> ErrorSourceViaOther<'a> for &ErrorSource<'a, E> {
+    fn error_source(&self) -> Option<&'a (dyn std::error::Error + 'static)> {
+        None
+    }
+}
@@ -0,0 +1,326 @@
+use crate::{Constraint, Describe, Validate};
+use std::path::{Path, PathBuf};
+
+/// The environment variable naming the directory that the tests generated by the `export_ts` attribute of the
+/// [PneuStr](crate::PneuStr) derive write TypeScript definitions to.  If it's not set, then nothing is written.
+pub const EXPORT_TS_DIR_VAR: &str = "PNEUTYPE_EXPORT_TS_DIR";
+
+/// Export of a pneutype as a TypeScript definition, so that a TypeScript frontend can enforce the same rules as
+/// the Rust backend.  This is implemented by the `export_ts` attribute of the [PneuStr](crate::PneuStr) derive, and
+/// gives a branded type, e.g.
+/// ```ts
+/// export type Lowercase = string & { __brand: "Lowercase" };
+/// ```
+/// along with a type guard, e.g. `isLowercase(s: string): s is Lowercase`, if the [Constraint] is declarative
+/// (i.e. it has no [Constraint::Custom] parts).  The definitions are written by `cargo test` (via a generated
+/// test) if the environment variable named by [EXPORT_TS_DIR_VAR] is set, or can be written by a build step via
+/// [ExportTs::export_ts_to].
+pub trait ExportTs: Describe {
+    /// The name of the TypeScript type, e.g. "Lowercase".
+    const TS_NAME: &'static str;
+    /// Returns the contents of the TypeScript module defining the type (and its type guard, if any).
+    fn ts_definition() -> String {
+        ts_definition(
+            Self::TYPE_NAME,
+            Self::TS_NAME,
+            &Self::constraint(),
+            &Self::description(),
+        )
+    }
+    /// Writes the TypeScript definition to the file named `<TS_NAME>.ts` in the given directory (creating the
+    /// directory if necessary), and returns the path of that file.
+    fn export_ts_to(dir: impl AsRef<Path>) -> std::io::Result<PathBuf> {
+        std::fs::create_dir_all(dir.as_ref())?;
+        let path = dir.as_ref().join(format!("{}.ts", Self::TS_NAME));
+        std::fs::write(&path, Self::ts_definition())?;
+        Ok(path)
+    }
+    /// Checks that [Validate] accepts a sample of the values that the TypeScript type guard accepts, and panics
+    /// otherwise.  The guard is built from [Describe::constraint] rather than from [Validate], so this catches a
+    /// constraint that's looser than the validation.  The sample is the [Constraint::OneOf] values and strings of
+    /// the boundary lengths made of the boundary chars of the [Constraint::Chars] class; parts of the constraint
+    /// that can't be evaluated in Rust (e.g. a [Constraint::Pattern]) are skipped.  This is called by the test
+    /// generated by the `export_ts` attribute.
+    fn check_ts_guard()
+    where
+        Self: Validate<Data = str>,
+    {
+        let constraint = Self::constraint();
+        if ts_condition(&constraint).is_none() {
+            return;
+        }
+        for sample in ts_samples(&constraint) {
+            if ts_accepts(&constraint, &sample) == Some(true) {
+                if let Err(err) = Self::validate(&sample) {
+                    panic!(
+                        "{:?} is accepted by the TypeScript guard is{} but isn't a valid {}: {}",
+                        sample,
+                        Self::TS_NAME,
+                        Self::TYPE_NAME,
+                        err
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn ts_definition(
+    type_name: &str,
+    ts_name: &str,
+    constraint: &Constraint,
+    description: &str,
+) -> String {
+    let mut definition = format!(
+        "// Generated by pneutype from {}.  Do not edit.\n\n/** {} */\nexport type {} = string & {{ __brand: {} }};\n",
+        type_name,
+        description.replace("*/", "* /"),
+        ts_name,
+        ts_string(ts_name)
+    );
+    if let Some((condition, _)) = ts_condition(constraint) {
+        definition.push_str(&format!(
+            "\n/** Returns true iff `s` is a valid {}. */\nexport function is{}(s: string): s is {} {{\n  return {};\n}}\n",
+            ts_name, ts_name, ts_name, condition
+        ));
+    }
+    definition
+}
+
+/// The precedence of a TypeScript boolean expression, for deciding whether it needs parentheses as an operand.
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+enum Precedence {
+    Or,
+    And,
+    Atom,
+}
+
+/// Returns the TypeScript boolean expression checking that `s` satisfies the constraint (along with its
+/// precedence), or None if the constraint isn't declarative.
+fn ts_condition(constraint: &Constraint) -> Option<(String, Precedence)> {
+    match constraint {
+        Constraint::Unconstrained => Some(("true".to_string(), Precedence::Atom)),
+        Constraint::Custom(_) => None,
+        Constraint::Length { unit, min, max } => {
+            let length = match *unit {
+                "bytes" => "new TextEncoder().encode(s).length",
+                "chars" => "[...s].length",
+                "graphemes" => "[...new Intl.Segmenter().segment(s)].length",
+                _ => return None,
+            };
+            let conditions = min
+                .map(|min| (format!("{} >= {}", length, min), Precedence::Atom))
+                .into_iter()
+                .chain(max.map(|max| (format!("{} <= {}", length, max), Precedence::Atom)))
+                .collect::<Vec<_>>();
+            Some(ts_join(conditions, Precedence::And))
+        }
+        Constraint::Chars { regex_class, .. } => regex_class.as_ref().map(|regex_class| {
+            (
+                format!("{}.test(s)", ts_regex(&format!("^[{}]*$", regex_class))),
+                Precedence::Atom,
+            )
+        }),
+        Constraint::Pattern(pattern) => Some((
+            format!("{}.test(s)", ts_regex(&format!("^(?:{})$", pattern))),
+            Precedence::Atom,
+        )),
+        Constraint::OneOf(values) => Some((
+            format!(
+                "[{}].includes(s)",
+                values
+                    .iter()
+                    .map(|value| ts_string(value))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Precedence::Atom,
+        )),
+        Constraint::AllOf(constraints) => Some(ts_join(
+            constraints
+                .iter()
+                .map(ts_condition)
+                .collect::<Option<Vec<_>>>()?,
+            Precedence::And,
+        )),
+        Constraint::AnyOf(constraints) => Some(ts_join(
+            constraints
+                .iter()
+                .map(ts_condition)
+                .collect::<Option<Vec<_>>>()?,
+            Precedence::Or,
+        )),
+        Constraint::Not(constraint) => Some((
+            format!(
+                "!{}",
+                ts_operand(ts_condition(constraint)?, Precedence::Atom)
+            ),
+            Precedence::Atom,
+        )),
+    }
+}
+
+/// Joins the conditions with `&&` or `||` (per `precedence`).  An empty conjunction is true, and an empty
+/// disjunction is false.
+fn ts_join(conditions: Vec<(String, Precedence)>, precedence: Precedence) -> (String, Precedence) {
+    let (operator, empty) = match precedence {
+        Precedence::And => (" && ", "true"),
+        _ => (" || ", "false"),
+    };
+    match conditions.len() {
+        0 => (empty.to_string(), Precedence::Atom),
+        1 => conditions.into_iter().next().unwrap(),
+        _ => (
+            conditions
+                .into_iter()
+                .map(|condition| ts_operand(condition, precedence))
+                .collect::<Vec<_>>()
+                .join(operator),
+            precedence,
+        ),
+    }
+}
+
+/// Returns the condition as an operand of an operator of the given precedence, parenthesized if necessary.
+fn ts_operand(
+    (condition, precedence): (String, Precedence),
+    operator_precedence: Precedence,
+) -> String {
+    if precedence < operator_precedence {
+        format!("({})", condition)
+    } else {
+        condition
+    }
+}
+
+/// Returns the TypeScript regex literal (with the `u` flag, so that it matches by code point) for the pattern.
+fn ts_regex(pattern: &str) -> String {
+    format!("/{}/u", pattern.replace('/', "\\/"))
+}
+
+/// Returns the TypeScript string literal for the value.
+fn ts_string(value: &str) -> String {
+    let mut literal = String::with_capacity(value.len() + 2);
+    literal.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            c if c.is_control() => literal.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// How much longer than its minimum length a sample string is, if its length has no upper bound.
+const SAMPLE_EXTRA_LEN: usize = 8;
+
+/// Returns the candidate values for [ExportTs::check_ts_guard], not all of which necessarily satisfy the constraint.
+fn ts_samples(constraint: &Constraint) -> Vec<String> {
+    let mut samples = match constraint {
+        Constraint::OneOf(values) => values.iter().map(|value| value.to_string()).collect(),
+        Constraint::AllOf(constraints) | Constraint::AnyOf(constraints) => {
+            constraints.iter().flat_map(ts_samples).collect()
+        }
+        _ => Vec::new(),
+    };
+    // A char is at least one byte and at most one grapheme, so these bounds don't exclude any valid string.
+    let (chars_min, chars_max) = constraint.length_bounds("chars");
+    let min = chars_min
+        .max(constraint.length_bounds("graphemes").0)
+        .unwrap_or(0);
+    let max = chars_max
+        .into_iter()
+        .chain(constraint.length_bounds("bytes").1)
+        .min()
+        .unwrap_or(min + SAMPLE_EXTRA_LEN)
+        .max(min);
+    let boundary_chars = ts_regex_class(constraint)
+        .and_then(crate::describe::class_ranges)
+        .map_or_else(
+            || vec!['a'],
+            |ranges| {
+                ranges
+                    .into_iter()
+                    .flat_map(|(first, last)| [first, last])
+                    .collect()
+            },
+        );
+    for len in [min, max] {
+        samples.extend(boundary_chars.iter().map(|c| c.to_string().repeat(len)));
+        samples.push(boundary_chars.iter().cycle().take(len).collect());
+    }
+    samples
+}
+
+/// Returns the regex bracket expression contents of the first char class that the constraint requires.
+fn ts_regex_class(constraint: &Constraint) -> Option<&str> {
+    match constraint {
+        Constraint::Chars { regex_class, .. } => regex_class.as_deref(),
+        Constraint::AllOf(constraints) => constraints.iter().find_map(ts_regex_class),
+        _ => None,
+    }
+}
+
+/// Returns whether the TypeScript guard built from the constraint accepts `s`, or None if that can't be determined
+/// in Rust.
+fn ts_accepts(constraint: &Constraint, s: &str) -> Option<bool> {
+    match constraint {
+        Constraint::Unconstrained => Some(true),
+        Constraint::Custom(_) | Constraint::Pattern(_) => None,
+        Constraint::Length { unit, min, max } => {
+            let length = match *unit {
+                "bytes" => s.len(),
+                "chars" => s.chars().count(),
+                #[cfg(feature = "unicode-segmentation")]
+                "graphemes" => {
+                    unicode_segmentation::UnicodeSegmentation::graphemes(s, true).count()
+                }
+                _ => return None,
+            };
+            Some(
+                !matches!(min, Some(min) if length < *min)
+                    && !matches!(max, Some(max) if length > *max),
+            )
+        }
+        Constraint::Chars { regex_class, .. } => {
+            let ranges = crate::describe::class_ranges(regex_class.as_deref()?)?;
+            Some(
+                s.chars()
+                    .all(|c| ranges.iter().any(|&(first, last)| first <= c && c <= last)),
+            )
+        }
+        Constraint::OneOf(values) => Some(values.iter().any(|value| value == s)),
+        Constraint::AllOf(constraints) => {
+            let accepts = constraints
+                .iter()
+                .map(|constraint| ts_accepts(constraint, s))
+                .collect::<Vec<_>>();
+            if accepts.contains(&Some(false)) {
+                Some(false)
+            } else {
+                accepts
+                    .into_iter()
+                    .collect::<Option<Vec<_>>>()
+                    .map(|_| true)
+            }
+        }
+        Constraint::AnyOf(constraints) => {
+            let accepts = constraints
+                .iter()
+                .map(|constraint| ts_accepts(constraint, s))
+                .collect::<Vec<_>>();
+            if accepts.contains(&Some(true)) {
+                Some(true)
+            } else {
+                accepts
+                    .into_iter()
+                    .collect::<Option<Vec<_>>>()
+                    .map(|_| false)
+            }
+        }
+        Constraint::Not(constraint) => ts_accepts(constraint, s).map(|accepts| !accepts),
+    }
+}
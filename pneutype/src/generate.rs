@@ -0,0 +1,276 @@
+//! Generation of valid values of pneutypes for property testing and fuzzing, as used by the `Arbitrary` impls that
+//! the derives generate with the `proptest` and `arbitrary` features.  Values are generated from the [Constraint]
+//! of the pneutype where it's declarative (i.e. chosen from [Constraint::OneOf] values, matching a
+//! [Constraint::Pattern], or made of [Constraint::Chars] within the [Constraint::Length] bounds), and otherwise
+//! from a seed supplied via the `proptest_seed` or `arbitrary_seed` attribute of the
+//! [PneuString](crate::PneuString) derive.  Either way, every candidate is checked with [Validate::validate] and
+//! only valid ones are kept, so the generated (and shrunk) values are always valid.
+//!
+//! A seed is required where the constraint can't be generated from, i.e. a [Constraint::Custom] (and also a
+//! [Constraint::Pattern] for `arbitrary`, which doesn't generate regex matches), since almost every candidate would
+//! otherwise be rejected.  If the constraint is known at compile time to be a [Constraint::Custom] (see
+//! [Describe::CONSTRAINT_IS_CUSTOM]), then a missing seed fails the build, and otherwise generation panics.
+
+use crate::{Constraint, Describe, Validate};
+use std::marker::PhantomData;
+
+/// How much longer than its minimum length a generated string can be, if its length has no upper bound.
+const DEFAULT_EXTRA_LEN: usize = 32;
+
+/// The way candidate strings are generated for a [Constraint].
+#[derive(Clone, Debug)]
+enum Plan {
+    /// One of the given values.
+    OneOf(Vec<String>),
+    /// Between `min` and `max` chars (inclusive), each from the given regex bracket expression contents, or any
+    /// char if there isn't one.
+    Chars {
+        regex_class: Option<String>,
+        min: usize,
+        max: usize,
+    },
+    /// A match of the given regex, or the given plan if regexes can't be generated from.
+    Pattern(String, Box<Plan>),
+    /// A candidate from one of the given plans.
+    AnyOf(Vec<Plan>),
+    /// A candidate from the seed.
+    Seed,
+}
+
+impl Plan {
+    fn new(constraint: &Constraint) -> Self {
+        match constraint {
+            Constraint::Custom(_) => Self::Seed,
+            Constraint::Pattern(pattern) => {
+                Self::Pattern(pattern.to_string(), Box::new(Self::chars(constraint)))
+            }
+            Constraint::OneOf(values) => {
+                Self::OneOf(values.iter().map(|value| value.to_string()).collect())
+            }
+            Constraint::AnyOf(constraints) if !constraints.is_empty() => {
+                Self::AnyOf(constraints.iter().map(Self::new).collect())
+            }
+            Constraint::AllOf(constraints) => {
+                // The most specific of the constraints drives the generation, and the rest are enforced by
+                // validation.
+                let plans = constraints.iter().map(Self::new).collect::<Vec<_>>();
+                let rank = |plan: &Self| match plan {
+                    Self::OneOf(_) => 0,
+                    Self::Pattern(..) => 1,
+                    Self::Seed => 2,
+                    Self::AnyOf(_) => 3,
+                    Self::Chars { .. } => 4,
+                };
+                match plans.into_iter().min_by_key(rank) {
+                    Some(Self::Chars { .. }) | None => Self::chars(constraint),
+                    Some(Self::Pattern(pattern, _)) => {
+                        Self::Pattern(pattern, Box::new(Self::chars(constraint)))
+                    }
+                    Some(plan) => plan,
+                }
+            }
+            _ => Self::chars(constraint),
+        }
+    }
+    /// Returns whether candidates can't be generated without a seed, where `patterns` is whether regex matches can
+    /// be generated.
+    fn needs_seed(&self, patterns: bool) -> bool {
+        match self {
+            Self::OneOf(_) | Self::Chars { .. } => false,
+            Self::Pattern(..) => !patterns,
+            Self::AnyOf(plans) => plans.iter().any(|plan| plan.needs_seed(patterns)),
+            Self::Seed => true,
+        }
+    }
+    /// Returns the plan generating chars within the length bounds of the constraint, from its char class if it
+    /// has one.
+    fn chars(constraint: &Constraint) -> Self {
+        // A char is at least one byte and at most one grapheme, so these bounds don't exclude any valid string.
+        let (chars_min, chars_max) = constraint.length_bounds("chars");
+        let min = chars_min
+            .max(constraint.length_bounds("graphemes").0)
+            .unwrap_or(0);
+        let max = chars_max
+            .into_iter()
+            .chain(constraint.length_bounds("bytes").1)
+            .min()
+            .unwrap_or(min + DEFAULT_EXTRA_LEN)
+            .max(min);
+        Self::Chars {
+            regex_class: regex_class(constraint),
+            min,
+            max,
+        }
+    }
+}
+
+/// Returns the regex bracket expression contents of the first char class that the constraint requires.
+fn regex_class(constraint: &Constraint) -> Option<String> {
+    match constraint {
+        Constraint::Chars { regex_class, .. } => regex_class.as_deref().map(str::to_string),
+        Constraint::AllOf(constraints) => constraints.iter().find_map(regex_class),
+        _ => None,
+    }
+}
+
+/// Compile-time checks, used by the `Arbitrary` impls generated by the [PneuString](crate::PneuString) derive
+/// without a seed, that the constraint of the pneutype `P` isn't known to be a [Constraint::Custom].
+#[doc(hidden)]
+pub struct RequireSeed<P: ?Sized>(PhantomData<P>);
+
+impl<P: Describe + ?Sized> RequireSeed<P> {
+    #[cfg(feature = "proptest")]
+    pub const PROPTEST: () = assert!(
+        !P::CONSTRAINT_IS_CUSTOM,
+        "values can't be generated from a Constraint::Custom, so the PneuString derive needs `proptest_seed`"
+    );
+    #[cfg(feature = "arbitrary")]
+    pub const ARBITRARY: () = assert!(
+        !P::CONSTRAINT_IS_CUSTOM,
+        "values can't be generated from a Constraint::Custom, so the PneuString derive needs `arbitrary_seed`"
+    );
+}
+
+/// Panics if the plan can't be generated from without a seed and there isn't one.
+fn require_seed<P: Describe + ?Sized>(
+    plan: &Plan,
+    patterns: bool,
+    has_seed: bool,
+    attribute: &str,
+) {
+    if plan.needs_seed(patterns) && !has_seed {
+        panic!(
+            "values of {} can't be generated from its constraint ({}), so the PneuString derive needs `{}`",
+            P::TYPE_NAME,
+            P::description(),
+            attribute
+        );
+    }
+}
+
+/// Returns the proptest strategy generating valid values of the pneutype `P` (as Strings), which shrinks to valid
+/// values.  The seed generates the candidates for the parts of the constraint that aren't declarative, so this
+/// panics if there are such parts but no seed.
+#[cfg(feature = "proptest")]
+pub fn proptest_strategy<P>(
+    seed: Option<proptest::strategy::BoxedStrategy<String>>,
+) -> proptest::strategy::BoxedStrategy<String>
+where
+    P: 'static + Validate<Data = str> + Describe + ?Sized,
+{
+    use proptest::strategy::Strategy;
+    let plan = Plan::new(&P::constraint());
+    require_seed::<P>(&plan, true, seed.is_some(), "proptest_seed");
+    let reason = format!("must be a valid {}", P::TYPE_NAME);
+    plan_strategy(&plan, seed)
+        .prop_filter(reason, |s| P::validate(s.as_str()).is_ok())
+        .boxed()
+}
+
+#[cfg(feature = "proptest")]
+fn plan_strategy(
+    plan: &Plan,
+    seed: Option<proptest::strategy::BoxedStrategy<String>>,
+) -> proptest::strategy::BoxedStrategy<String> {
+    use proptest::strategy::Strategy;
+    match plan {
+        Plan::OneOf(values) if !values.is_empty() => {
+            proptest::sample::select(values.clone()).boxed()
+        }
+        Plan::OneOf(_) => proptest::strategy::Just(String::new()).boxed(),
+        Plan::Chars {
+            regex_class,
+            min,
+            max,
+        } => {
+            let atom = regex_class.as_ref().map_or_else(
+                || "(?s:.)".to_string(),
+                |regex_class| format!("[{}]", regex_class),
+            );
+            // If the char class isn't valid regex syntax, then any char is generated and validation filters.
+            proptest::string::string_regex(&format!("{}{{{},{}}}", atom, min, max))
+                .ok()
+                .or_else(|| {
+                    proptest::string::string_regex(&format!("(?s:.){{{},{}}}", min, max)).ok()
+                })
+                .expect("programmer error: chars regex must be valid")
+                .boxed()
+        }
+        Plan::Pattern(pattern, fallback) => match proptest::string::string_regex(pattern) {
+            Ok(strategy) => strategy.boxed(),
+            Err(_) => plan_strategy(fallback, seed),
+        },
+        Plan::AnyOf(plans) => proptest::strategy::Union::new(
+            plans
+                .iter()
+                .map(|plan| plan_strategy(plan, seed.clone()))
+                .collect::<Vec<_>>(),
+        )
+        .boxed(),
+        Plan::Seed => seed.expect("programmer error: a seed must have been required"),
+    }
+}
+
+/// A function generating candidate strings from fuzzer input, as given by the `arbitrary_seed` attribute of the
+/// [PneuString](crate::PneuString) derive.
+#[cfg(feature = "arbitrary")]
+pub type ArbitrarySeed = fn(&mut arbitrary::Unstructured<'_>) -> arbitrary::Result<String>;
+
+/// Generates a valid value of the pneutype `P` (as a String) from the fuzzer input.  The seed generates the
+/// candidates for the parts of the constraint that aren't declarative (including patterns), so this panics if there
+/// are such parts but no seed.  An invalid candidate gives [arbitrary::Error::IncorrectFormat], which fuzzers (e.g.
+/// `cargo fuzz`) treat as an input to skip.
+#[cfg(feature = "arbitrary")]
+pub fn arbitrary_string<P>(
+    u: &mut arbitrary::Unstructured<'_>,
+    seed: Option<ArbitrarySeed>,
+) -> arbitrary::Result<String>
+where
+    P: Validate<Data = str> + Describe + ?Sized,
+{
+    let plan = Plan::new(&P::constraint());
+    require_seed::<P>(&plan, false, seed.is_some(), "arbitrary_seed");
+    let s = plan_arbitrary(&plan, u, seed)?;
+    P::validate(s.as_str()).map_err(|_| arbitrary::Error::IncorrectFormat)?;
+    Ok(s)
+}
+
+#[cfg(feature = "arbitrary")]
+fn plan_arbitrary(
+    plan: &Plan,
+    u: &mut arbitrary::Unstructured<'_>,
+    seed: Option<ArbitrarySeed>,
+) -> arbitrary::Result<String> {
+    use arbitrary::Arbitrary;
+    match plan {
+        Plan::OneOf(values) => u.choose(values).cloned(),
+        Plan::Chars {
+            regex_class,
+            min,
+            max,
+        } => {
+            let ranges = regex_class
+                .as_deref()
+                .and_then(crate::describe::class_ranges);
+            let len = u.int_in_range(*min..=*max)?;
+            (0..len)
+                .map(|_| match ranges.as_deref() {
+                    Some(ranges) => {
+                        let &(first, last) = u.choose(ranges)?;
+                        char::from_u32(u.int_in_range(first as u32..=last as u32)?)
+                            .ok_or(arbitrary::Error::IncorrectFormat)
+                    }
+                    None => char::arbitrary(u),
+                })
+                .collect()
+        }
+        // Regexes aren't generated from here, so a pattern relies on the seed just as an opaque constraint does.
+        Plan::Pattern(..) | Plan::Seed => {
+            seed.expect("programmer error: a seed must have been required")(u)
+        }
+        Plan::AnyOf(plans) => {
+            let index = u.choose_index(plans.len())?;
+            plan_arbitrary(&plans[index], u, seed)
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use crate::Constraint;
+use serde_json::{json, Map, Value};
+
+impl Constraint {
+    /// Returns the JSON schema for strings satisfying this constraint, i.e. `"type": "string"` along with whichever
+    /// of `minLength`, `maxLength`, `pattern` and `enum` express it.  Constraints that can't be expressed (e.g.
+    /// [Constraint::Custom]) are omitted, so the schema may accept more than the constraint does, but never less.
+    /// This is used by the `JsonSchema` impls of the derives, which add the [Describe](crate::Describe)
+    /// description.
+    #[cfg(feature = "schemars")]
+    pub fn json_schema(&self) -> schemars::Schema {
+        let mut schema = self.json_schema_keywords();
+        schema.insert("type".to_string(), json!("string"));
+        schema.into()
+    }
+    /// Returns the JSON schema keywords expressing this constraint (not including `type`).
+    pub(crate) fn json_schema_keywords(&self) -> Map<String, Value> {
+        let mut schema = Map::new();
+        self.apply_to_json_schema(&mut schema);
+        schema
+    }
+    /// Adds the keywords expressing this constraint to the given schema, which doesn't necessarily have a type.
+    fn apply_to_json_schema(&self, schema: &mut Map<String, Value>) {
+        match self {
+            Self::Unconstrained | Self::Custom(_) => {}
+            Self::Length { unit, min, max } => {
+                let (min, max) = json_schema_length_bounds(unit, *min, *max);
+                if let Some(min) = min {
+                    let min = schema
+                        .get("minLength")
+                        .and_then(Value::as_u64)
+                        .map_or(min as u64, |existing| existing.max(min as u64));
+                    schema.insert("minLength".to_string(), json!(min));
+                }
+                if let Some(max) = max {
+                    let max = schema
+                        .get("maxLength")
+                        .and_then(Value::as_u64)
+                        .map_or(max as u64, |existing| existing.min(max as u64));
+                    schema.insert("maxLength".to_string(), json!(max));
+                }
+            }
+            Self::Chars { regex_class, .. } => {
+                if let Some(regex_class) = regex_class {
+                    add_pattern(schema, format!("^[{}]*$", regex_class));
+                }
+            }
+            Self::Pattern(pattern) => add_pattern(schema, format!("^(?:{})$", pattern)),
+            Self::OneOf(values) => {
+                let values = values
+                    .iter()
+                    .map(|value| Value::String(value.to_string()))
+                    .collect::<Vec<_>>();
+                match schema.get("enum").and_then(Value::as_array) {
+                    // Only the values in both are allowed.
+                    Some(existing) => {
+                        let values = values
+                            .into_iter()
+                            .filter(|value| existing.contains(value))
+                            .collect::<Vec<_>>();
+                        schema.insert("enum".to_string(), Value::Array(values));
+                    }
+                    None => {
+                        schema.insert("enum".to_string(), Value::Array(values));
+                    }
+                }
+            }
+            Self::AllOf(constraints) => {
+                for constraint in constraints {
+                    constraint.apply_to_json_schema(schema);
+                }
+            }
+            Self::AnyOf(constraints) => {
+                // An alternative that can't be expressed accepts anything, and so does the whole.
+                let alternatives = constraints
+                    .iter()
+                    .map(|constraint| {
+                        let mut alternative = Map::new();
+                        constraint.apply_to_json_schema(&mut alternative);
+                        alternative
+                    })
+                    .collect::<Vec<_>>();
+                if !alternatives.is_empty()
+                    && alternatives
+                        .iter()
+                        .all(|alternative| !alternative.is_empty())
+                {
+                    add_subschema(schema, "anyOf", json!(alternatives));
+                }
+            }
+            Self::Not(constraint) => {
+                // The keywords of the negated constraint may accept more than it does, in which case their negation
+                // would reject valid strings.  So the negation is only expressed if they're exact.
+                let mut negated = Map::new();
+                constraint.apply_to_json_schema(&mut negated);
+                if !negated.is_empty() && constraint.is_exact_in_json_schema() {
+                    add_subschema(schema, "not", Value::Object(negated));
+                }
+            }
+        }
+    }
+    /// Returns true iff the JSON schema keywords expressing this constraint accept exactly the strings that it
+    /// does, rather than more.
+    fn is_exact_in_json_schema(&self) -> bool {
+        match self {
+            Self::Unconstrained | Self::Pattern(_) | Self::OneOf(_) => true,
+            Self::Custom(_) => false,
+            // In another unit than chars, only the bounds that mean the same in every unit are exact.
+            Self::Length { unit, min, max } => {
+                *unit == "chars"
+                    || (!matches!(min, Some(min) if *min > 1)
+                        && !matches!(max, Some(max) if *max > 0))
+            }
+            Self::Chars { regex_class, .. } => regex_class.is_some(),
+            Self::AllOf(constraints) | Self::AnyOf(constraints) => {
+                constraints.iter().all(Self::is_exact_in_json_schema)
+            }
+            Self::Not(constraint) => constraint.is_exact_in_json_schema(),
+        }
+    }
+}
+
+/// Returns the bounds of `minLength` and `maxLength` expressing the given length bounds in the given unit.  JSON
+/// schema measures length in chars.  A char is at most one grapheme and at least one byte, so only the bounds that
+/// don't reject valid strings are kept, apart from those that mean the same in every unit, i.e. a `min` of at most
+/// 1 (non-empty) and a `max` of 0 (empty).
+fn json_schema_length_bounds(
+    unit: &str,
+    min: Option<usize>,
+    max: Option<usize>,
+) -> (Option<usize>, Option<usize>) {
+    let any_unit_min = min.filter(|&min| min <= 1);
+    let any_unit_max = max.filter(|&max| max == 0);
+    match unit {
+        "chars" => (min, max),
+        "bytes" => (any_unit_min, max),
+        "graphemes" => (min, any_unit_max),
+        _ => (any_unit_min, any_unit_max),
+    }
+}
+
+/// Sets the `pattern` of the schema, or if it already has one, requires the given one in addition via `allOf`.
+fn add_pattern(schema: &mut Map<String, Value>, pattern: String) {
+    if schema.contains_key("pattern") {
+        add_subschema(schema, "allOf", json!([{ "pattern": pattern }]));
+    } else {
+        schema.insert("pattern".to_string(), json!(pattern));
+    }
+}
+
+/// Sets the given keyword of the schema, or if it already has it, requires the given subschema in addition via
+/// `allOf`.  For `allOf` itself, `subschema` is an array of subschemas to append.
+fn add_subschema(schema: &mut Map<String, Value>, keyword: &str, subschema: Value) {
+    if keyword == "allOf" {
+        let all_of = schema
+            .entry("allOf")
+            .or_insert_with(|| json!([]))
+            .as_array_mut()
+            .expect("programmer error: allOf must be an array");
+        all_of.extend(subschema.as_array().cloned().unwrap_or_default());
+    } else if schema.contains_key(keyword) {
+        add_subschema(schema, "allOf", json!([{ keyword: subschema }]));
+    } else {
+        schema.insert(keyword.to_string(), subschema);
+    }
+}
@@ -10,12 +10,61 @@
 //!
 //! Appropriate, natural impls of of [Borrow](std::borrow::Borrow), [Deref](std::ops::Deref),
 //! [Display](std::fmt::Display), [From], [FromStr](std::str::FromStr), [ToOwned], and [TryFrom] are provided for the
-//! newtypes, so that they can still transparently be used as if they were [String] and [str].
+//! newtypes, so that they can still transparently be used as if they were [String] and [str].  Where that's
+//! unwanted (e.g. the [str] API leaking onto opaque IDs, or displaying secrets), the `no_deref`, `no_display` and
+//! `no_from_str` attributes opt out of the respective impls (which the [PneuString] and [PneuStr] traits don't
+//! require, whereas [PneuStringExt] and [PneuStrExt] do), and the `vis` attribute restricts the visibility of the
+//! generated inherent methods (though not of unchecked construction, which stays public via the traits).
+//!
+//! Migrating from 0.5: the [Deref](std::ops::Deref), [Display](std::fmt::Display) and [FromStr](std::str::FromStr)
+//! bounds of the [PneuString] trait, along with its `FromStrErr` associated type, have moved to [PneuStringExt], and
+//! the [Display](std::fmt::Display) bound of the [PneuStr] trait has moved to [PneuStrExt].  Both are implemented
+//! automatically, so derived types are unaffected (unless they use the opt-out attributes), but generic code
+//! relying on those impls must bound on `T: PneuStringExt` (e.g. for `T::FromStrErr`) or `T: PneuStrExt` instead.
+//!
+//! Constraints that depend on runtime context (e.g. a max length from a config file) can additionally be defined
+//! using the [ValidateWith] trait, which is used by the `new_ref_with` and `try_from_with` constructors (and with
+//! the `serde` feature, the `pneutype::serde::ContextSeed` and `pneutype::serde::BorrowedContextSeed`
+//! deserializers), so that one pneutype definition can serve differently configured deployments.
+//!
+//! When a constraint is tightened, data written under the old constraint can still be loaded by implementing
+//! [ValidateLenient], which accepts the legacy forms and canonicalizes them into the strict form.  This is used by
+//! the `from_legacy` constructor (and the `deserialize = "lenient"` attribute, and with the `serde` feature, the
+//! `pneutype::serde::lenient` field attribute), so that migrations happen automatically upon load.
+//!
+//! Constraints with a canonical form (e.g. lowercase hostnames) can implement [Normalize], which maps input to the
+//! canonical form before it's validated.  This is used by the `normalize_from` and `try_normalize` constructors (and
+//! the `deserialize = "normalize"` attribute, and with the `serde` feature, the `pneutype::serde::normalize` field
+//! attribute), while validation itself stays strict.
+//!
+//! Where there's an obvious repair strategy (e.g. replacing forbidden chars or truncating), [Sanitize] can be
+//! implemented, which is used by the infallible `sanitize` and `sanitize_with_report` constructors, the latter
+//! also returning a [SanitizeReport] of what was changed.
+//!
+//! A PneuStr with a `const fn validate_const` can be constructed from a literal at compile time, with invalid
+//! literals failing to compile, via the [pneu_str!] macro.  This also works in `const` and `static` items.
+//!
+//! For constraints that can be assembled from common rules, the generic [ValidatedStr] and [ValidatedString] are
+//! parametrized by the validator marker types in [validators] (e.g. `And<MinLen<1>, CharsAll<AsciiLowercase>>`),
+//! so that a new pneutype is just a type alias.  In particular, [BoundedStr] and [BoundedString] (and their
+//! byte- and grapheme-measured relatives) bound the length, e.g. `BoundedString<1, 255>`.
+//!
+//! A validator's error type can be anything implementing [Debug](std::fmt::Debug) and [Display](std::fmt::Display),
+//! but [ValidationError] is provided as a structured error carrying the failing rule, the byte range of the
+//! offending part of the input, the offending char and what was expected, which the constructors and serde errors
+//! then surface (see [ValidationError::underline] for pointing at the offending part).
+//!
+//! To report every failure at once (e.g. for form input), validators can implement [Validate::validate_all], which
+//! is used by the `try_from_collect_errors` and `new_ref_collect_errors` constructors.  Among the [validators],
+//! [And](validators::And) reports the failures of both of its sides and [CharsAll](validators::CharsAll) reports
+//! every offending char, while the rest (e.g. [Or](validators::Or), whose error pairs a failure of each side, and
+//! [Not](validators::Not)) report a single failure via the default implementation.
 //!
 //! Minimal example:
 //! ```
 //! #[derive(pneutype::PneuString)]
 //! #[pneu_string(borrow = "ThingyStr")]
+//! # #[pneu_string(no_arbitrary)]
 //! struct Thingy(String);
 //!
 //! impl Thingy {
@@ -75,6 +124,7 @@
 //! ```
 //! #[derive(Debug, PartialEq, pneutype::PneuString, serde::Serialize)]
 //! #[pneu_string(borrow = "SplungeStr", deserialize)]
+//! # #[pneu_string(no_arbitrary)]
 //! struct Splunge(String);
 //!
 //! #[derive(Debug, PartialEq, pneutype::PneuStr, serde::Serialize)]
@@ -88,7 +138,7 @@
 //!     fn validate(data: &Self::Data) -> Result<(), Self::Error> {
 //!         // Let's define a Splunge to be a non-empty string.
 //!         if data.is_empty() {
-//!             Err("a Splunge must a non-empty")
+//!             Err("a Splunge must be non-empty")
 //!         } else {
 //!             Ok(())
 //!         }
@@ -115,12 +165,89 @@
 //!     assert_eq!(document_deserialized, document);
 //! }
 //! ```
+//!
+//! The generated impls of [serde::Deserialize](https://docs.rs/serde/latest/serde/trait.Deserialize.html) also
+//! work for map keys, e.g. `HashMap<Splunge, V>` and `BTreeMap<&SplungeStr, V>`, for formats that give keys as
+//! strings (e.g. JSON).  For formats that give integer-looking keys as integers, use the map `with`-modules in the
+//! `serde` module (e.g. `pneutype::serde::hash_map_owned`), which accept integer keys in their string form.  A
+//! pneutype itself never accepts an integer, so e.g. a bare `123` value is still rejected.  Validation errors name
+//! the type and the offending value, e.g.
+//! `invalid value: string "", expected a SplungeStr (a Splunge must be non-empty)`.  Note that a borrowed [PneuStr]
+//! can only be deserialized if the deserializer can lend out the string directly from the input, so for example a
+//! JSON string containing escape sequences has to be deserialized as the owned [PneuString].
+//!
+//! Every derived pneutype implements [Describe], which gives its name and a [Constraint] describing what it
+//! accepts (machine-readable for the [validators], and otherwise as given by the `constraint` attribute), so that
+//! serde error messages (via the bare `expecting` attribute), schemas, CLI help, and error messages can all draw
+//! from one source.
+//!
+//! A [PneuStr] with the `export_ts` attribute implements [ExportTs], which gives a branded TypeScript type along
+//! with a type guard enforcing the same (declarative) constraint, so that a TypeScript frontend can share the
+//! validated string types of a Rust backend.
+//!
+//! The `schemars` feature implements `schemars::JsonSchema` for every derived pneutype (both the [PneuString]
+//! and its [PneuStr] have the same schema), which is `"type": "string"` with whichever of `minLength`, `maxLength`,
+//! `pattern` and `enum` express its [Constraint] (see [Constraint::json_schema]), plus its [Describe] description
+//! and an optional `format` given by the `schema_format` attribute (and likewise for `schema_description`).
+//!
+//! The `utoipa` feature likewise implements `utoipa::PartialSchema` and `utoipa::ToSchema` for every derived
+//! pneutype, with the same constraint metadata (see [Constraint::openapi_schema]), so that pneutypes show up in
+//! generated OpenAPI documents as constrained strings.  The schema of a generic pneutype is named after its
+//! [Describe::TYPE_NAME] and its generic arguments (see [Describe::generic_type_name]), e.g.
+//! `ValidatedStr_Bounded_chars_1_8`, so its type arguments must implement [Describe].
+//!
+//! The `miette` feature implements `miette::Diagnostic` for [ValidationError], labeling the offending part of the
+//! input, and enables the `diagnostic` attribute for the error types generated by the `error` attribute, which
+//! supply the rejected input as the source code, so that CLIs can render rich error reports.
+//!
+//! The `proptest` and `arbitrary` features implement `proptest::arbitrary::Arbitrary` and `arbitrary::Arbitrary`
+//! respectively for every derived [PneuString], generating only valid values (see the `generate` module).  Values
+//! are generated directly from declarative [Constraint]s (e.g. [Constraint::OneOf] or [Constraint::Pattern]), and
+//! otherwise candidates from a seed (see the `proptest_seed` and `arbitrary_seed` attributes) are rejection-sampled,
+//! so that property tests, their shrinking, and fuzzing (e.g. via `cargo fuzz`) only ever see valid values.  A
+//! PneuString whose PneuStr has neither a `constraint` nor a `one_of` attribute thus fails to build with these
+//! features unless it has a seed (or `no_arbitrary`).
+//!
+//! The `testing` feature provides the `testing` module, a conformance test kit checking the properties every
+//! pneutype should have (e.g. that `new_ref` agrees with `try_from`, and that [Hash](std::hash::Hash) is consistent
+//! with [Borrow](std::borrow::Borrow)`<str>`), with the `pneutype_conformance!` macro generating the whole suite of
+//! tests from lists of valid and invalid strings.  It's meant to be enabled only in `[dev-dependencies]`.
+//!
+//! The `serde` feature enables the `pneutype::serde` module, which provides `with`-modules and
+//! [DeserializeSeed](https://docs.rs/serde/latest/serde/de/trait.DeserializeSeed.html)s for containers of pneutypes,
+//! e.g. for deserializing `Cow<'a, SplungeStr>` as borrowed where possible and as owned otherwise.
+
+// This makes the derives usable within this crate, since their generated code refers to `pneutype::...`.
+extern crate self as pneutype;
 
 mod as_str;
+mod bounded;
+mod describe;
+mod error_source;
+mod export_ts;
+#[cfg(any(feature = "proptest", feature = "arbitrary"))]
+pub mod generate;
+#[cfg(any(feature = "schemars", feature = "utoipa"))]
+mod json_schema;
 mod new_ref_unchecked;
+mod normalize;
+#[cfg(feature = "utoipa")]
+mod openapi;
 mod pneu_str;
+mod pneu_str_literal;
 mod pneu_string;
+mod refines;
+mod sanitize;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "testing")]
+pub mod testing;
 mod validate;
+mod validate_lenient;
+mod validate_with;
+mod validated;
+mod validation_error;
+pub mod validators;
 
 /// This will implement traits appropriate for a String-based newtype, which will be referred to generally as a "PneuString".
 /// A [PneuString] always has a corresponding [PneuStr].  Trait implementation details for [PneuString] should be given via
@@ -128,6 +255,7 @@ mod validate;
 /// ```
 /// #[derive(pneutype::PneuString)]
 /// #[pneu_string(borrow = "ThingyStr")]
+/// # #[pneu_string(no_arbitrary)]
 /// pub struct Thingy(String);
 ///
 /// #[derive(pneutype::PneuStr)]
@@ -155,6 +283,66 @@ mod validate;
 /// -   deserialize -- if present, then the proc-macro will generate an implementation of
 ///     [serde::Deserialize](https://docs.rs/serde/latest/serde/trait.Deserialize.html)
 ///     performs the expected validation (in particular, returning error if the validation constraints are not met).
+///     `deserialize = "strict"` is equivalent.  `deserialize = "lenient"` instead deserializes using `from_legacy`,
+///     i.e. accepting legacy forms and canonicalizing them into the strict form (see [ValidateLenient]).
+///     `deserialize = "normalize"` instead deserializes using `try_normalize`, i.e. normalizing before validating
+///     (see [Normalize]).
+/// -   expecting = "..." -- optional human-readable description of what the [PneuString] accepts, used in the
+///     error messages of the generated [serde::Deserialize](https://docs.rs/serde/latest/serde/trait.Deserialize.html),
+///     e.g. `invalid value: string "Foo", expected a Thingy (an even number of chars)`.  The bare `expecting`
+///     attribute uses the description given by [Describe] instead.  If not specified, then the validation error is
+///     used in its place.
+/// -   consts(NAME, ...) -- optionally names consts defined via the `consts` attribute of the corresponding
+///     [PneuStr], for each of which a constructor is generated, e.g. `consts(DEFAULT, ADMIN)` generates
+///     `Thingy::default_value()` and `Thingy::admin_value()`.
+/// -   default = "NAME" -- optionally implements [Default] using the named const of the corresponding [PneuStr].
+/// -   refines = "..." -- optionally specifies the parent [PneuString] that this one refines, whose [PneuStr] must
+///     be refined by this one's [PneuStr] (see [Refines]).  This generates [From] for the parent and [TryFrom] from
+///     the parent, which reuse the [String] allocation and only check the refinement.
+/// -   error = "..." -- optionally names an error type to generate (for a [PneuString] without generics), e.g.
+///     `error = "InvalidThingy"`, which is used as the error of the [std::str::FromStr], `TryFrom<&str>` and
+///     `TryFrom<String>` impls.  It has public `input` and `source` fields holding the rejected input and the
+///     validation error, a [Display](std::fmt::Display) that names the type (e.g.
+///     `invalid Thingy "Foo": ThingyStr must have an even number of chars`), and implements [std::error::Error],
+///     whose `source()` is the validation error if that implements [std::error::Error] too.
+/// -   diagnostic -- if present (along with `error`), then `miette::Diagnostic` is implemented for the generated
+///     error type, using the rejected input as the source code and forwarding the rest to the validation error,
+///     which must implement `miette::Diagnostic` (e.g. [ValidationError]).  Requires the `miette` feature.
+/// -   no_describe -- if present, then [Describe] isn't implemented.  Otherwise it's implemented with the name of the
+///     [PneuString] and the [Constraint] of the corresponding [PneuStr].
+/// -   no_schema -- if present, then `schemars::JsonSchema` and `utoipa::ToSchema` aren't implemented with the
+///     `schemars` and `utoipa` features, e.g. so that they can be implemented manually.
+/// -   no_arbitrary -- if present, then `proptest::arbitrary::Arbitrary` and `arbitrary::Arbitrary` aren't
+///     implemented with the `proptest` and `arbitrary` features, e.g. so that they can be implemented manually.
+/// -   str_cmp -- if present, then [Eq], [Ord] and [Hash](std::hash::Hash) are implemented to agree exactly with
+///     those of [str] (as its [Borrow](std::borrow::Borrow)`<str>` impl requires), without any bounds on generic
+///     parameters, along with [PartialEq] and [PartialOrd] (in both directions) between the [PneuString] and its
+///     [PneuStr], `&` its [PneuStr], [str], `&str`, [String] and `Cow<str>`.  So e.g. `map.get("abc")` and
+///     `thingy == "abc"` work.  These traits must then not be derived.
+/// -   no_deref -- if present, then [Deref](std::ops::Deref) isn't implemented.  The [PneuStr] is still available
+///     via `as_pneu_str`, [AsRef] and [Borrow](std::borrow::Borrow).
+/// -   no_display -- if present, then [Display](std::fmt::Display) isn't implemented, e.g. for secrets.
+/// -   no_from_str -- if present, then [FromStr](std::str::FromStr) isn't implemented, e.g. so that it can be
+///     implemented manually.
+///
+///     The [PneuString] trait doesn't require these impls, so it's still implemented, along with everything that
+///     builds on it.  Only [PneuStringExt] (which requires all three) isn't.
+/// -   vis(method = "...", ...) -- optionally overrides the (otherwise `pub`) visibility of the named generated
+///     inherent methods, e.g. `vis(new_unchecked = "pub(crate)", new_unchecked_const = "pub(crate)")`, where an
+///     empty visibility makes the method private.  These can be `new_unchecked`, `new_unchecked_const`,
+///     `try_from_collect_errors`, `try_from_with`, `from_legacy`, `normalize_from`, `try_normalize`, `sanitize`,
+///     `sanitize_with_report`, `as_pneu_str` (regardless of its name), `as_str` and `into_string`.  Only the
+///     inherent methods are restricted, since the trait impls are always public.  In particular, `vis` can't
+///     restrict `new_unchecked`, which remains available as the trait method [PneuString::new_unchecked] wherever
+///     the [PneuString] is visible, so unchecked construction can't be made private this way.
+/// -   proptest_seed = "..." -- names a function `fn() -> proptest::strategy::BoxedStrategy<String>` generating
+///     candidates for the `proptest::arbitrary::Arbitrary` impl generated with the `proptest` feature, which is
+///     required for constraints that values can't be generated from directly (i.e. [Constraint::Custom]).  Invalid
+///     candidates are rejected.  Without it, such a constraint fails the build if it's known at compile time (see
+///     [Describe::CONSTRAINT_IS_CUSTOM]), and otherwise generation panics.
+/// -   arbitrary_seed = "..." -- names a function of type `pneutype::generate::ArbitrarySeed` generating
+///     candidates for the `arbitrary::Arbitrary` impl generated with the `arbitrary` feature, analogous to
+///     `proptest_seed`, except that it's also required for a [Constraint::Pattern].
 ///
 /// Note that [serde::Serialize](https://docs.rs/serde/latest/serde/trait.Serialize.html) can be implemented
 /// directly on the [PneuString] via the standard derive.
@@ -189,12 +377,197 @@ pub use pneutype_derive::PneuString;
 /// -   deserialize -- if present, then the proc-macro will generate an implementation of
 ///     [serde::Deserialize](https://docs.rs/serde/latest/serde/trait.Deserialize.html)
 ///     performs the expected validation (in particular, returning error if the validation constraints are not met).
+/// -   expecting = "..." -- optional human-readable description of what the [PneuStr] accepts, used in the
+///     error messages of the generated [serde::Deserialize](https://docs.rs/serde/latest/serde/trait.Deserialize.html),
+///     e.g. `invalid value: string "Foo", expected a ThingyStr (an even number of chars)`.  The bare `expecting`
+///     attribute uses the description given by [Describe] instead.  If not specified, then the validation error is
+///     used in its place.
+/// -   consts(NAME = "...", ...) -- optionally defines named constants, e.g. `consts(DEFAULT = "default")`
+///     generates `pub const DEFAULT: &'static ThingyStr`.  These are validated at compile time, so for a [PneuStr]
+///     without generics an invalid value fails the build even if the const is never used (with generics, a const
+///     is only evaluated where it's used).  This requires `validate_const` (unless `one_of` is specified, which
+///     generates it).
+/// -   validate_const -- declares that the [PneuStr] has an inherent
+///     `const fn validate_const(s: &str) -> Result<(), &'static str>` (see [pneu_str!]), which is used to validate
+///     the consts at compile time.
+///
+/// For example, this doesn't compile, since `SHOUT` isn't all-lowercase, although it's never used:
+/// ```compile_fail
+/// #[derive(pneutype::PneuStr)]
+/// #[pneu_str(consts(SHOUT = "NOT LOWER"), validate_const)]
+/// #[repr(transparent)]
+/// pub struct LowerStr(str);
+///
+/// impl LowerStr {
+///     pub const fn validate_const(s: &str) -> Result<(), &'static str> {
+///         let bytes = s.as_bytes();
+///         let mut i = 0;
+///         while i < bytes.len() {
+///             if !bytes[i].is_ascii_lowercase() {
+///                 return Err("LowerStr must be all-lowercase ASCII");
+///             }
+///             i += 1;
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// impl pneutype::Validate for LowerStr {
+///     type Data = str;
+///     type Error = &'static str;
+///     fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+///         LowerStr::validate_const(data)
+///     }
+/// }
+/// ```
+/// -   default = "NAME" -- optionally implements [Default] for `&ThingyStr` using the named const.
+/// -   one_of("...", ...) -- optionally restricts the [PneuStr] to a fixed set of values, e.g.
+///     `one_of("dev", "staging", "prod")`, in which case [Validate] must not be implemented manually.  This
+///     generates the [Validate] impl, `validate_const`, a const for each value (e.g. `DEV`), an `all()` iterator,
+///     and an enum with a variant for each value (e.g. `Dev`) for exhaustive matching, along with `kind()` and
+///     conversions to and from it.  The names are derived from the ASCII-alphanumeric words of each value, so the
+///     values must give distinct names that aren't keywords, e.g. `one_of("foo-bar", "foo_bar")`,
+///     `one_of("Dev", "dev")` and `one_of("self")` (whose variant would be `Self`) are rejected.
+///
+/// For example, this doesn't compile, since both values give the const `FOO_BAR` and the variant `FooBar`:
+/// ```compile_fail
+/// #[derive(pneutype::PneuStr)]
+/// #[pneu_str(one_of("foo-bar", "foo_bar"))]
+/// #[repr(transparent)]
+/// pub struct FooBarStr(str);
+/// ```
+/// -   enum_name = "..." -- optionally specifies the name of the enum generated by `one_of`.  If not specified, then
+///     it's the name of the [PneuStr] with any `Str` suffix replaced by `Kind`, e.g. `ThingyKind`.
+/// -   refines = "..." -- optionally specifies the parent [PneuStr] that this one refines (e.g. a `SlugStr` that
+///     refines `LowercaseStr`), in which case [Refines] must be implemented for the parent instead of implementing
+///     [Validate].  This generates the [Validate] impl (checking the parent's constraint first), [AsRef] and [From]
+///     for the parent (which don't revalidate), and [TryFrom] from the parent (which only checks the refinement).
+///     The `str`-valued field may instead be of the parent type, e.g. `struct SlugStr(LowercaseStr);`, but
+///     `refines` must still be specified, since refinement impls are only generated on request.
+/// -   error = "..." -- optionally names an error type to generate (for a [PneuStr] without generics), e.g.
+///     `error = "InvalidThingyStr"`, which is used as the error of the `TryFrom<&str>` impl.  See the same attribute
+///     of [PneuString].
+/// -   diagnostic -- if present (along with `error`), then `miette::Diagnostic` is implemented for the generated
+///     error type.  See the same attribute of [PneuString].
+/// -   constraint = "..." -- optionally specifies an expression of type [Constraint] describing what the [PneuStr]
+///     accepts, for the generated [Describe] impl, e.g.
+///     `constraint = "pneutype::Constraint::Pattern(\"[0-9a-f]+\".into())"`.  If not specified, then the constraint
+///     is given by `one_of` or `expecting = "..."` if present, and otherwise is described only by the name of the
+///     [PneuStr].
+/// -   no_describe -- if present, then [Describe] isn't implemented, e.g. so that it can be implemented manually.
+/// -   no_schema -- if present, then `schemars::JsonSchema` and `utoipa::ToSchema` aren't implemented with the
+///     `schemars` and `utoipa` features.  See the same attribute of [PneuString].
+/// -   str_cmp -- if present, then [Eq], [Ord] and [Hash](std::hash::Hash) are implemented to agree exactly with
+///     those of [str], along with [PartialEq] and [PartialOrd] (in both directions) between the [PneuStr] (and `&`
+///     it) and [str], `&str`, [String] and `Cow<str>`.  See the same attribute of [PneuString].
+/// -   no_deref -- if present, then [Deref](std::ops::Deref)`<Target = str>` isn't implemented, e.g. so that the
+///     [str] API doesn't leak onto opaque IDs.  The [str] is still available via `as_str`.
+/// -   no_display -- if present, then [Display](std::fmt::Display) isn't implemented, e.g. for secrets.  The
+///     [PneuStr] trait doesn't require it, so only [PneuStrExt] isn't implemented.
+/// -   vis(method = "...", ...) -- optionally overrides the (otherwise `pub`) visibility of the named generated
+///     inherent methods, e.g. `vis(new_ref_unchecked = "pub(crate)")`.  These can be `new_ref`,
+///     `new_ref_collect_errors`, `new_ref_with`, `new_ref_unchecked`, `new_ref_unchecked_const` and `as_str`.  Only
+///     the inherent methods are restricted, since the trait impls are always public.  In particular, `vis` can't
+///     restrict `new_ref_unchecked`, which remains available as the trait method
+///     [NewRefUnchecked::new_ref_unchecked] (just as `new_ref` does as [PneuStr::new_ref]) wherever the [PneuStr]
+///     is visible, so unchecked construction can't be made private this way.  Note also that [pneu_str!] requires
+///     `new_ref_unchecked_const` to be visible.
+///
+/// For example, an opaque ID whose [str] API isn't exposed:
+/// ```compile_fail
+/// #[derive(pneutype::PneuStr)]
+/// #[pneu_str(no_deref)]
+/// #[repr(transparent)]
+/// pub struct UserIdStr(str);
+///
+/// impl pneutype::Validate for UserIdStr {
+///     type Data = str;
+///     type Error = &'static str;
+///     fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+///         Ok(())
+///     }
+/// }
+///
+/// let user_id = UserIdStr::new_ref("u-123").unwrap();
+/// assert_eq!(user_id.as_str(), "u-123");
+/// // This doesn't compile, since there's no Deref<Target = str>.
+/// user_id.len();
+/// ```
+/// -   export_ts -- if present, then [ExportTs] is implemented, with the TypeScript type named after the [PneuStr],
+///     or e.g. `export_ts = "Thingy"` to name it `Thingy` instead, which must be a valid TypeScript identifier.  A
+///     test is generated (for a [PneuStr] without generics) which checks that [Validate] accepts a sample of the
+///     values that the type guard accepts (see [ExportTs::check_ts_guard]), and writes the TypeScript definition
+///     to the directory named by the [EXPORT_TS_DIR_VAR] environment variable, if it's set, e.g.
+///     `PNEUTYPE_EXPORT_TS_DIR=frontend/src/types cargo test`.
+///
+/// For example, this doesn't compile, since `my-type` isn't a TypeScript identifier:
+/// ```compile_fail
+/// #[derive(pneutype::PneuStr)]
+/// #[pneu_str(export_ts = "my-type")]
+/// #[repr(transparent)]
+/// pub struct MyTypeStr(str);
+///
+/// impl pneutype::Validate for MyTypeStr {
+///     type Data = str;
+///     type Error = &'static str;
+///     fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+///         Ok(())
+///     }
+/// }
+/// ```
+/// -   schema_format = "..." -- optionally specifies the `format` of the JSON schema generated with the `schemars`
+///     feature (and of the OpenAPI schema generated with the `utoipa` feature), e.g. `schema_format = "hostname"`.
+/// -   schema_description = "..." -- optionally specifies the `description` of the JSON schema generated with the
+///     `schemars` feature (and of the OpenAPI schema generated with the `utoipa` feature), in place of the
+///     [Describe] description.
 ///
 /// Note that [serde::Serialize](https://docs.rs/serde/latest/serde/trait.Serialize.html) can be implemented
 /// directly on the [PneuStr] via the standard derive.
 pub use pneutype_derive::PneuStr;
 
+// This is used by the code generated by the derives with the `schemars` feature.
+#[cfg(feature = "schemars")]
+#[doc(hidden)]
+pub use schemars;
+
+// This is used by the code generated by the derives with the `utoipa` feature.
+#[cfg(feature = "utoipa")]
+#[doc(hidden)]
+pub use utoipa;
+
+// These are used by the code generated by the derives with the `proptest` and `arbitrary` features.
+#[cfg(feature = "arbitrary")]
+#[doc(hidden)]
+pub use arbitrary;
+#[cfg(feature = "proptest")]
+#[doc(hidden)]
+pub use proptest;
+
+// These are used by the code generated for the `error` attribute.
+#[doc(hidden)]
+pub use crate::error_source::{ErrorSource, ErrorSourceViaError, ErrorSourceViaOther};
+
+// This is used by the code generated for the `diagnostic` attribute.
+#[cfg(feature = "miette")]
+#[doc(hidden)]
+pub use miette;
+
+#[cfg(feature = "unicode-segmentation")]
+pub use crate::bounded::{BoundedGraphemesStr, BoundedGraphemesString};
 pub use crate::{
-    as_str::AsStr, new_ref_unchecked::NewRefUnchecked, pneu_str::PneuStr, pneu_string::PneuString,
+    as_str::AsStr,
+    bounded::{BoundedBytesStr, BoundedBytesString, BoundedStr, BoundedString},
+    describe::{Constraint, Describe},
+    export_ts::{ExportTs, EXPORT_TS_DIR_VAR},
+    new_ref_unchecked::NewRefUnchecked,
+    normalize::Normalize,
+    pneu_str::{PneuStr, PneuStrExt},
+    pneu_string::{PneuString, PneuStringExt},
+    refines::Refines,
+    sanitize::{Sanitize, SanitizeChange, SanitizeReport},
     validate::Validate,
+    validate_lenient::ValidateLenient,
+    validate_with::ValidateWith,
+    validated::{ValidatedStr, ValidatedString},
+    validation_error::ValidationError,
 };
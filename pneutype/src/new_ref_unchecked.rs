@@ -1,7 +1,12 @@
 use crate::Validate;
 
+/// Provides construction of a reference to a [PneuStr](crate::PneuStr) from its input without validation.
 pub trait NewRefUnchecked {
     type Input: ?Sized;
+    /// Wrap the given input as a reference to this type without validating it.
+    ///
+    /// # Safety
+    /// The caller must guarantee that the input satisfies the validation constraint of this type.
     unsafe fn new_ref_unchecked(input: &Self::Input) -> &Self;
 }
 
@@ -13,6 +18,6 @@ impl NewRefUnchecked for str {
             "programmer error: new_ref_unchecked was passed invalid data"
         );
         // See https://stackoverflow.com/questions/64977525/how-can-i-create-newtypes-for-an-unsized-type-and-its-owned-counterpart-like-s
-        &*(input as *const str as *const Self)
+        input
     }
 }
@@ -0,0 +1,22 @@
+use crate::{PneuString, Validate};
+use std::borrow::Cow;
+
+/// Used to declare the canonical form of the data, e.g. lowercasing a hostname, collapsing whitespace, or dropping
+/// a trailing slash from a path.  Normalization is an explicit step which happens before validation, and is used by
+/// the `normalize_from` and `try_normalize` constructors of a PneuString and by its derived serde::Deserialize
+/// under `deserialize = "normalize"`.  Validation itself stays strict, i.e. it should reject non-normalized data.
+pub trait Normalize: Validate<Data = str> {
+    /// Map the given data to its normalized form.  Data that is already normalized should be returned as is, so
+    /// that no allocation is needed in the common case.
+    fn normalize(data: Cow<'_, str>) -> Cow<'_, str>;
+}
+
+/// A PneuString inherits its PneuStr's impl of Normalize.
+impl<T: PneuString> Normalize for T
+where
+    <T as PneuString>::Borrowed: Normalize,
+{
+    fn normalize(data: Cow<'_, str>) -> Cow<'_, str> {
+        <<T as PneuString>::Borrowed as Normalize>::normalize(data)
+    }
+}
@@ -0,0 +1,50 @@
+use crate::Constraint;
+use serde_json::Value;
+use utoipa::openapi::schema::{Object, ObjectBuilder, Type};
+
+impl Constraint {
+    /// Returns the OpenAPI schema for strings satisfying this constraint, i.e. type `string` along with whichever
+    /// of `minLength`, `maxLength`, `pattern` and `enum` express it (as in [Constraint::json_schema], except that
+    /// multiple patterns are combined into one, and negations and alternatives are omitted).  This is used by the
+    /// `ToSchema` impls of the derives, which add the [Describe](crate::Describe) description.
+    pub fn openapi_schema(&self) -> Object {
+        let keywords = self.json_schema_keywords();
+        let length = |keyword: &str| {
+            keywords
+                .get(keyword)
+                .and_then(Value::as_u64)
+                .map(|length| length as usize)
+        };
+        let patterns = keywords
+            .get("pattern")
+            .into_iter()
+            .chain(
+                keywords
+                    .get("allOf")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|subschema| subschema.get("pattern")),
+            )
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>();
+        // The patterns are all anchored, so requiring each of them is a matter of looking ahead from the start.
+        let pattern = match patterns.as_slice() {
+            [] => None,
+            [pattern] => Some(pattern.to_string()),
+            patterns => Some(
+                patterns
+                    .iter()
+                    .map(|pattern| format!("(?={})", pattern))
+                    .collect::<String>(),
+            ),
+        };
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .min_length(length("minLength"))
+            .max_length(length("maxLength"))
+            .pattern(pattern)
+            .enum_values(keywords.get("enum").and_then(Value::as_array).cloned())
+            .build()
+    }
+}
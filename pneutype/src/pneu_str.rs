@@ -1,15 +1,24 @@
-use crate::{AsStr, NewRefUnchecked, Validate};
+use crate::{AsStr, NewRefUnchecked, Validate, ValidateWith};
 
 pub trait PneuStr:
     AsRef<str>
     + AsStr
     + std::borrow::Borrow<str>
-    + std::fmt::Display
     + NewRefUnchecked<Input = str>
     + Validate<Data = str, Error = Self::ValidateError>
 {
     type ValidateError: std::fmt::Debug + std::fmt::Display;
     fn new_ref(s: &str) -> Result<&Self, <Self as Validate>::Error>;
+    /// Validate the given str, reporting every failure instead of just the first (see [Validate::validate_all]),
+    /// and wrap it as a reference.
+    fn new_ref_collect_errors(s: &str) -> Result<&Self, Vec<<Self as Validate>::Error>>;
+    /// Validate the given str, including against the given runtime context, and wrap it as a reference.
+    fn new_ref_with<'s, Ctx: ?Sized>(
+        s: &'s str,
+        ctx: &Ctx,
+    ) -> Result<&'s Self, <Self as Validate>::Error>
+    where
+        Self: ValidateWith<Ctx>;
 }
 
 /// Automatic implementation of PneuStr for any type that implements appropriate traits.
@@ -18,7 +27,6 @@ where
     T: AsRef<str>
         + AsStr
         + std::borrow::Borrow<str>
-        + std::fmt::Display
         + NewRefUnchecked<Input = str>
         + ?Sized
         + Validate<Data = str>,
@@ -29,4 +37,27 @@ where
         <Self as Validate>::validate(s)?;
         unsafe { Ok(Self::new_ref_unchecked(s)) }
     }
+    fn new_ref_collect_errors(s: &str) -> Result<&Self, Vec<<Self as Validate>::Error>> {
+        <Self as Validate>::validate_all(s)?;
+        unsafe { Ok(Self::new_ref_unchecked(s)) }
+    }
+    fn new_ref_with<'s, Ctx: ?Sized>(
+        s: &'s str,
+        ctx: &Ctx,
+    ) -> Result<&'s Self, <Self as Validate>::Error>
+    where
+        Self: ValidateWith<Ctx>,
+    {
+        <Self as Validate>::validate(s)?;
+        <Self as ValidateWith<Ctx>>::validate_with(s, ctx)?;
+        unsafe { Ok(Self::new_ref_unchecked(s)) }
+    }
 }
+
+/// A [PneuStr] that also has the std impl that the derive generates unless told otherwise, i.e. [Display] (see the
+/// `no_display` attribute), for generic code that relies on it.  This is implemented automatically.
+///
+/// [Display]: std::fmt::Display
+pub trait PneuStrExt: PneuStr + std::fmt::Display {}
+
+impl<T> PneuStrExt for T where T: PneuStr + std::fmt::Display + ?Sized {}
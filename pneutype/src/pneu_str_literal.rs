@@ -0,0 +1,90 @@
+/// Produces a `&'static T` for a [PneuStr](crate::PneuStr) `T` from a string literal (or any other const `&str`),
+/// failing to compile if the literal is invalid.  This requires `T` to have an inherent
+/// `const fn validate_const(s: &str) -> Result<(), &'static str>`, which defines the same constraint as its
+/// [Validate](crate::Validate) impl (typically the latter just calls the former).  Because the result is a
+/// constant, this also works in `const` and `static` items.  Note that Rust doesn't allow constants of unsized type
+/// (such as a PneuStr) as match patterns, so in match arms they have to be compared in a guard (or the match done on
+/// `as_str()` against plain literals).
+///
+/// Invoke it as `pneutype::pneu_str!`, since importing it would make it ambiguous with the `pneu_str` attribute of
+/// the [PneuStr](macro@crate::PneuStr) derive.
+/// ```
+/// #[derive(Debug, Eq, PartialEq, pneutype::PneuStr)]
+/// #[repr(transparent)]
+/// struct LowercaseStr(str);
+///
+/// impl LowercaseStr {
+///     pub const fn validate_const(s: &str) -> Result<(), &'static str> {
+///         let bytes = s.as_bytes();
+///         let mut i = 0;
+///         while i < bytes.len() {
+///             if !bytes[i].is_ascii_lowercase() {
+///                 return Err("LowercaseStr must be all-lowercase ASCII");
+///             }
+///             i += 1;
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// impl pneutype::Validate for LowercaseStr {
+///     type Data = str;
+///     type Error = &'static str;
+///     fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+///         Self::validate_const(data)
+///     }
+/// }
+///
+/// const HELLO: &LowercaseStr = pneutype::pneu_str!(LowercaseStr, "hello");
+/// static WORLD: &LowercaseStr = pneutype::pneu_str!(LowercaseStr, "world");
+///
+/// fn greeting(s: &LowercaseStr) -> bool {
+///     match s {
+///         s if s == HELLO => true,
+///         _ => false,
+///     }
+/// }
+///
+/// assert!(greeting(LowercaseStr::new_ref("hello").unwrap()));
+/// assert!(!greeting(WORLD));
+/// assert_eq!(pneutype::pneu_str!(LowercaseStr, "abc").as_str(), "abc");
+/// ```
+///
+/// An invalid literal is a compile error:
+/// ```compile_fail
+/// # #[derive(pneutype::PneuStr)]
+/// # #[repr(transparent)]
+/// # struct LowercaseStr(str);
+/// # impl LowercaseStr {
+/// #     pub const fn validate_const(s: &str) -> Result<(), &'static str> {
+/// #         let bytes = s.as_bytes();
+/// #         let mut i = 0;
+/// #         while i < bytes.len() {
+/// #             if !bytes[i].is_ascii_lowercase() {
+/// #                 return Err("LowercaseStr must be all-lowercase ASCII");
+/// #             }
+/// #             i += 1;
+/// #         }
+/// #         Ok(())
+/// #     }
+/// # }
+/// # impl pneutype::Validate for LowercaseStr {
+/// #     type Data = str;
+/// #     type Error = &'static str;
+/// #     fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+/// #         Self::validate_const(data)
+/// #     }
+/// # }
+/// let x = pneutype::pneu_str!(LowercaseStr, "Hello");
+/// ```
+#[macro_export]
+macro_rules! pneu_str {
+    ($t:ty, $s:expr $(,)?) => {{
+        const VALUE: &'static $t = match <$t>::validate_const($s) {
+            // Safe because the validation constraint was just checked.
+            ::std::result::Result::Ok(()) => unsafe { <$t>::new_ref_unchecked_const($s) },
+            ::std::result::Result::Err(err) => panic!("{}", err),
+        };
+        VALUE
+    }};
+}
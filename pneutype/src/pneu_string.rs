@@ -1,4 +1,6 @@
-use crate::{AsStr, PneuStr};
+use crate::{
+    AsStr, Normalize, PneuStr, Sanitize, SanitizeReport, Validate, ValidateLenient, ValidateWith,
+};
 
 pub trait PneuString:
     AsRef<Self::Borrowed>
@@ -6,22 +8,105 @@ pub trait PneuString:
     + AsStr
     + std::borrow::Borrow<Self::Borrowed>
     + std::borrow::Borrow<str>
-    + std::ops::Deref<Target = Self::Borrowed>
-    + std::fmt::Display
-    + std::str::FromStr<Err = Self::FromStrErr>
     + TryFrom<String, Error = Self::TryFromStringErr>
 {
     type Borrowed: PneuStr + ?Sized;
-    type FromStrErr: std::fmt::Debug + std::fmt::Display;
     type TryFromStringErr: std::fmt::Debug + std::fmt::Display;
+    /// Construct this PneuString from a String without validating it.
+    ///
+    /// # Safety
+    /// The caller must guarantee that the String satisfies the validation constraint of this type.
     unsafe fn new_unchecked(s: String) -> Self;
     fn as_pneu_str(&self) -> &Self::Borrowed;
     fn into_string(self) -> String;
+    /// Validate the given string, reporting every failure instead of just the first (see
+    /// [Validate::validate_all]), and construct this PneuString from it.
+    fn try_from_collect_errors<S>(s: S) -> Result<Self, Vec<<Self::Borrowed as Validate>::Error>>
+    where
+        Self: Sized,
+        S: AsRef<str> + Into<String>,
+    {
+        <Self::Borrowed as Validate>::validate_all(s.as_ref())?;
+        unsafe { Ok(Self::new_unchecked(s.into())) }
+    }
+    /// Validate the given string, including against the given runtime context, and construct this PneuString
+    /// from it.  The string is only converted into a String if it's valid.
+    fn try_from_with<S, Ctx>(s: S, ctx: &Ctx) -> Result<Self, <Self::Borrowed as Validate>::Error>
+    where
+        Self: Sized,
+        S: AsRef<str> + Into<String>,
+        Ctx: ?Sized,
+        Self::Borrowed: ValidateWith<Ctx>,
+    {
+        <Self::Borrowed as Validate>::validate(s.as_ref())?;
+        <Self::Borrowed as ValidateWith<Ctx>>::validate_with(s.as_ref(), ctx)?;
+        unsafe { Ok(Self::new_unchecked(s.into())) }
+    }
+    /// Validate the given string in lenient mode (see [ValidateLenient]), canonicalize it into the strict form,
+    /// and construct this PneuString from that.  The strict form is validated as well, as a safeguard.
+    fn from_legacy<S>(s: S) -> Result<Self, <Self::Borrowed as Validate>::Error>
+    where
+        Self: Sized,
+        S: AsRef<str> + Into<String>,
+        Self::Borrowed: ValidateLenient,
+    {
+        <Self::Borrowed as ValidateLenient>::validate_lenient(s.as_ref())?;
+        let canonical = match <Self::Borrowed as ValidateLenient>::canonicalize(s.as_ref()) {
+            std::borrow::Cow::Borrowed(_) => None,
+            std::borrow::Cow::Owned(canonical) => Some(canonical),
+        };
+        let s = canonical.unwrap_or_else(|| s.into());
+        <Self::Borrowed as Validate>::validate(s.as_str())?;
+        unsafe { Ok(Self::new_unchecked(s)) }
+    }
+    /// Normalize the given string (see [Normalize]), validate the normalized form, and construct this PneuString
+    /// from that.
+    fn normalize_from(s: &str) -> Result<Self, <Self::Borrowed as Validate>::Error>
+    where
+        Self: Sized,
+        Self::Borrowed: Normalize,
+    {
+        let s =
+            <Self::Borrowed as Normalize>::normalize(std::borrow::Cow::Borrowed(s)).into_owned();
+        <Self::Borrowed as Validate>::validate(s.as_str())?;
+        unsafe { Ok(Self::new_unchecked(s)) }
+    }
+    /// Same as [PneuString::normalize_from], but reuses the given String's allocation where possible.
+    fn try_normalize(s: String) -> Result<Self, <Self::Borrowed as Validate>::Error>
+    where
+        Self: Sized,
+        Self::Borrowed: Normalize,
+    {
+        let s = <Self::Borrowed as Normalize>::normalize(std::borrow::Cow::Owned(s)).into_owned();
+        <Self::Borrowed as Validate>::validate(s.as_str())?;
+        unsafe { Ok(Self::new_unchecked(s)) }
+    }
+    /// Repair the given string into a valid form (see [Sanitize]) and construct this PneuString from that.  This
+    /// panics if the [Sanitize] impl produces invalid data, since that's a bug in the impl.
+    fn sanitize(s: &str) -> Self
+    where
+        Self: Sized,
+        Self::Borrowed: Sanitize,
+    {
+        Self::sanitize_with_report(s).0
+    }
+    /// Same as [PneuString::sanitize], but also returns a report of what was changed.
+    fn sanitize_with_report(s: &str) -> (Self, SanitizeReport)
+    where
+        Self: Sized,
+        Self::Borrowed: Sanitize,
+    {
+        let mut report = SanitizeReport::new();
+        let s = <Self::Borrowed as Sanitize>::sanitize(s, &mut report).into_owned();
+        if let Err(err) = <Self::Borrowed as Validate>::validate(s.as_str()) {
+            panic!("Sanitize impl produced invalid data {:?}: {}", s, err);
+        }
+        (unsafe { Self::new_unchecked(s) }, report)
+    }
 }
 
 impl PneuString for String {
     type Borrowed = str;
-    type FromStrErr = <Self as std::str::FromStr>::Err;
     type TryFromStringErr = <Self as TryFrom<String>>::Error;
     unsafe fn new_unchecked(s: String) -> Self {
         s
@@ -33,3 +118,24 @@ impl PneuString for String {
         self
     }
 }
+
+/// A [PneuString] that also has the std impls that the derive generates unless told otherwise, i.e.
+/// [Deref](std::ops::Deref) to its [PneuStr], [Display](std::fmt::Display) and [FromStr](std::str::FromStr) (see the
+/// `no_deref`, `no_display` and `no_from_str` attributes), for generic code that relies on them.  This is
+/// implemented automatically.
+pub trait PneuStringExt:
+    PneuString
+    + std::ops::Deref<Target = Self::Borrowed>
+    + std::fmt::Display
+    + std::str::FromStr<Err = Self::FromStrErr>
+{
+    type FromStrErr: std::fmt::Debug + std::fmt::Display;
+}
+
+impl<T> PneuStringExt for T
+where
+    T: PneuString + std::ops::Deref<Target = T::Borrowed> + std::fmt::Display + std::str::FromStr,
+    <T as std::str::FromStr>::Err: std::fmt::Debug + std::fmt::Display,
+{
+    type FromStrErr = <T as std::str::FromStr>::Err;
+}
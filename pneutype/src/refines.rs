@@ -0,0 +1,13 @@
+use crate::Validate;
+
+/// Used to declare that a PneuStr is a refinement of a parent PneuStr, i.e. that every valid value of the former
+/// is also a valid value of the parent (e.g. `SlugStr` refines `LowercaseStr`).  The parent is specified via the
+/// `refines` attribute of the PneuStr derive, which then generates the impl of [Validate] (checking the parent's
+/// constraint first, then [Refines::validate_refinement]), along with the upward conversions that don't need to
+/// revalidate and the checked downward conversions.
+pub trait Refines<Parent: Validate<Data = str> + ?Sized> {
+    /// The error type of the generated impl of [Validate], which must be able to represent the parent's errors.
+    type Error: std::fmt::Debug + std::fmt::Display + From<Parent::Error>;
+    /// Validate the additional constraint, given data that is already known to be valid for the parent.
+    fn validate_refinement(data: &str) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,131 @@
+use crate::{PneuString, Validate};
+use std::{borrow::Cow, ops::Range};
+
+/// Used to define an infallible, lossy repair of data into a valid form, e.g. slugifying a title, replacing
+/// forbidden chars with `_`, or truncating to the max length on a char boundary.  This is used by the `sanitize`
+/// and `sanitize_with_report` constructors of a PneuString.  Validation itself stays strict.
+pub trait Sanitize: Validate<Data = str> {
+    /// Whether the repair truncates the data (possibly among other changes), in which case
+    /// [And](crate::validators::And) applies it after the other repair, so that the length isn't spent on chars
+    /// that are removed anyway.
+    const TRUNCATES: bool = false;
+    /// Repair the given data so that it's accepted by [Validate::validate], recording each change in the given
+    /// [SanitizeReport].  Data that is already valid should be returned as [Cow::Borrowed] and leave the report
+    /// empty.
+    fn sanitize<'a>(data: &'a str, report: &mut SanitizeReport) -> Cow<'a, str>;
+}
+
+/// A PneuString inherits its PneuStr's impl of Sanitize.
+impl<T: PneuString> Sanitize for T
+where
+    <T as PneuString>::Borrowed: Sanitize,
+{
+    const TRUNCATES: bool = <<T as PneuString>::Borrowed as Sanitize>::TRUNCATES;
+    fn sanitize<'a>(data: &'a str, report: &mut SanitizeReport) -> Cow<'a, str> {
+        <<T as PneuString>::Borrowed as Sanitize>::sanitize(data, report)
+    }
+}
+
+/// A single change made by [Sanitize::sanitize].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SanitizeChange {
+    /// The byte range of the changed part of the data passed to the step that made this change.  Note that this
+    /// is relative to that step's input, not to the original data, if earlier steps already changed it.
+    pub range: Range<usize>,
+    /// What the changed part was replaced with (empty if it was removed).
+    pub replacement: String,
+    /// Human-readable reason for the change, e.g. "forbidden char".
+    pub reason: &'static str,
+}
+
+/// Records what [Sanitize::sanitize] changed, so that callers can e.g. warn the user that their input was altered.
+/// Also provides helpers for the common repair strategies, which record their changes as they go.
+///
+/// The changes are listed in the order they were made.  Ranges aren't remapped between steps, so when a repair is
+/// made of several steps (e.g. truncating and then replacing chars, or [And](crate::validators::And) of two
+/// repairs), each change's range refers to the data as it was after the earlier steps, and the ranges of
+/// different steps can't be compared directly.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SanitizeReport {
+    changes: Vec<SanitizeChange>,
+}
+
+impl SanitizeReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Returns true iff nothing was changed, i.e. the data was already valid.
+    pub fn is_unchanged(&self) -> bool {
+        self.changes.is_empty()
+    }
+    pub fn changes(&self) -> &[SanitizeChange] {
+        &self.changes
+    }
+    /// Record a change made by a custom repair strategy.
+    pub fn record(
+        &mut self,
+        range: Range<usize>,
+        replacement: impl Into<String>,
+        reason: &'static str,
+    ) {
+        self.changes.push(SanitizeChange {
+            range,
+            replacement: replacement.into(),
+            reason,
+        });
+    }
+    /// Replace each char for which `is_forbidden` returns true with `replacement`.
+    pub fn replace_chars<'a>(
+        &mut self,
+        data: &'a str,
+        is_forbidden: impl Fn(char) -> bool,
+        replacement: char,
+        reason: &'static str,
+    ) -> Cow<'a, str> {
+        if !data.chars().any(&is_forbidden) {
+            return Cow::Borrowed(data);
+        }
+        let mut sanitized = String::with_capacity(data.len());
+        for (i, c) in data.char_indices() {
+            if is_forbidden(c) {
+                self.record(i..i + c.len_utf8(), replacement, reason);
+                sanitized.push(replacement);
+            } else {
+                sanitized.push(c);
+            }
+        }
+        Cow::Owned(sanitized)
+    }
+    /// Truncate to at most `max_chars` chars.
+    pub fn truncate_chars<'a>(
+        &mut self,
+        data: &'a str,
+        max_chars: usize,
+        reason: &'static str,
+    ) -> Cow<'a, str> {
+        match data.char_indices().nth(max_chars) {
+            Some((i, _)) => {
+                self.record(i..data.len(), "", reason);
+                Cow::Borrowed(&data[..i])
+            }
+            None => Cow::Borrowed(data),
+        }
+    }
+    /// Truncate to at most `max_bytes` bytes, on a char boundary.
+    pub fn truncate_bytes<'a>(
+        &mut self,
+        data: &'a str,
+        max_bytes: usize,
+        reason: &'static str,
+    ) -> Cow<'a, str> {
+        if data.len() <= max_bytes {
+            return Cow::Borrowed(data);
+        }
+        let mut i = max_bytes;
+        while !data.is_char_boundary(i) {
+            i -= 1;
+        }
+        self.record(i..data.len(), "", reason);
+        Cow::Borrowed(&data[..i])
+    }
+}
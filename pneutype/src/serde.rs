@@ -0,0 +1,1262 @@
+//! Helpers for deserializing containers of pneutypes, borrowing from the input where possible and falling back to
+//! the owned [PneuString] where not.  Requires the `serde` feature.
+//!
+//! Fields of type `&'a XyzStr`, `Option<&'a XyzStr>`, `Vec<&'a XyzStr>`, etc. only need `#[serde(borrow)]`, given
+//! the `deserialize` attribute on the [PneuStr] derive.  However, those fail to deserialize when
+//! the deserializer can't lend out the string (e.g. a JSON string containing escape sequences).  The usual remedy
+//! is `Cow<'a, XyzStr>`, but serde always deserializes `Cow` as owned.  The `with`-modules here deserialize `Cow`
+//! (and containers of it) as borrowed if possible and as owned otherwise, and only take a single attribute:
+//! ```
+//! use std::{borrow::Cow, collections::HashSet};
+//!
+//! # #[derive(Debug, Eq, Hash, PartialEq, pneutype::PneuString, serde::Serialize)]
+//! # #[pneu_string(borrow = "NameStr", deserialize)]
+//! # #[pneu_string(no_arbitrary)]
+//! # struct Name(String);
+//! # #[derive(Debug, Eq, Hash, PartialEq, pneutype::PneuStr, serde::Serialize)]
+//! # #[pneu_str(deserialize)]
+//! # #[repr(transparent)]
+//! # struct NameStr(str);
+//! # impl pneutype::Validate for NameStr {
+//! #     type Data = str;
+//! #     type Error = &'static str;
+//! #     fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+//! #         if data.is_empty() { Err("must be non-empty") } else { Ok(()) }
+//! #     }
+//! # }
+//! #[derive(serde::Deserialize, serde::Serialize)]
+//! struct Document<'a> {
+//!     #[serde(borrow, with = "pneutype::serde::cow")]
+//!     name: Cow<'a, NameStr>,
+//!     #[serde(borrow, default, with = "pneutype::serde::option_cow")]
+//!     nickname: Option<Cow<'a, NameStr>>,
+//!     #[serde(borrow, with = "pneutype::serde::vec_cow")]
+//!     aliases: Vec<Cow<'a, NameStr>>,
+//!     #[serde(borrow, with = "pneutype::serde::hash_set_cow")]
+//!     tags: HashSet<Cow<'a, NameStr>>,
+//! }
+//!
+//! let json = r#"{"name":"Bob","aliases":["Robert","Bob\tby"],"tags":["x"]}"#;
+//! let document: Document = serde_json::from_str(json).expect("pass");
+//! assert!(matches!(document.name, Cow::Borrowed(_)));
+//! assert!(document.nickname.is_none());
+//! assert!(matches!(document.aliases[0], Cow::Borrowed(_)));
+//! // This one contained an escape sequence, so it couldn't be borrowed.
+//! assert!(matches!(document.aliases[1], Cow::Owned(_)));
+//! ```
+//!
+//! The same shapes of plain borrowed and owned pneutypes (`&'a XyzStr` and `XyzString`) have the `with`-modules
+//! [borrowed], [option_borrowed], [vec_borrowed], [hash_set_borrowed], and [owned], [option_owned], [vec_owned],
+//! [hash_set_owned].  These don't fall back to anything, but they only rely on the [PneuStr] and [PneuString]
+//! traits, so they work whether or not the `deserialize` attribute was given to the derive:
+//! ```
+//! # #[derive(Debug, Eq, Hash, PartialEq, pneutype::PneuString)]
+//! # #[pneu_string(borrow = "NameStr")]
+//! # #[pneu_string(no_arbitrary)]
+//! # struct Name(String);
+//! # #[derive(Debug, Eq, Hash, PartialEq, pneutype::PneuStr)]
+//! # #[repr(transparent)]
+//! # struct NameStr(str);
+//! # impl pneutype::Validate for NameStr {
+//! #     type Data = str;
+//! #     type Error = &'static str;
+//! #     fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+//! #         if data.is_empty() { Err("must be non-empty") } else { Ok(()) }
+//! #     }
+//! # }
+//! #[derive(serde::Deserialize, serde::Serialize)]
+//! struct Team<'a> {
+//!     #[serde(borrow, default, with = "pneutype::serde::option_borrowed")]
+//!     lead: Option<&'a NameStr>,
+//!     #[serde(with = "pneutype::serde::vec_owned")]
+//!     members: Vec<Name>,
+//! }
+//!
+//! let team: Team = serde_json::from_str(r#"{"lead":"Ann","members":["Bob","C\u0061t"]}"#).expect("pass");
+//! assert_eq!(team.lead.map(|lead| lead.as_str()), Some("Ann"));
+//! assert_eq!(team.members[1].as_str(), "Cat");
+//! assert!(serde_json::from_str::<Team>(r#"{"members":[""]}"#).is_err());
+//! ```
+//!
+//! Maps keyed by a [PneuString] have the `with`-modules [hash_map_owned] and [btree_map_owned], which also accept
+//! integer keys (validated in their string form), as given by formats that produce integer-looking keys as
+//! integers (see [MapKeySeed]).  The derived serde::Deserialize impls only accept strings, so a plain
+//! `HashMap<XyzString, V>` field works with formats that give keys as strings (e.g. JSON).
+//!
+//! The [DeserializeSeed] implementations [CowSeed], [BorrowedSeed], [OwnedSeed], [OptionSeed], [VecSeed],
+//! [HashSetSeed], [HashMapSeed], [BTreeMapSeed] and [MapKeySeed] are what the `with`-modules are built from, and
+//! can be composed for other shapes (e.g. `VecSeed(OptionSeed(CowSeed::new()))`) or with any other
+//! [DeserializeSeed] (e.g. [PhantomData] for ordinary [Deserialize] types).
+//!
+//! Errors name the pneutype and what it expects the same way the derived serde::Deserialize impls do, which is
+//! drawn from [Describe], so the seeds and `with`-modules require it (the derives implement it unless
+//! `no_describe` is given).
+//!
+//! [ContextSeed] and [BorrowedContextSeed] carry a runtime context for pneutypes whose constraint depends on it (see
+//! [ValidateWith]), and compose with the container seeds in the same way, e.g.
+//! `VecSeed(BorrowedContextSeed::<TenantStr, _>::new(&allow_list))`.
+//!
+//! [LenientSeed] (and the [lenient] `with`-module) accept legacy forms and canonicalize them (see
+//! [ValidateLenient]), so that a single field can opt into lenient deserialization regardless of how the
+//! PneuString's own serde::Deserialize was derived.  Likewise, [NormalizeSeed] (and the [normalize] `with`-module)
+//! normalize the input before validating it (see [Normalize]).
+
+use crate::{
+    AsStr, Describe, Normalize, PneuStr, PneuString, Validate, ValidateLenient, ValidateWith,
+};
+use ::serde::{
+    de::{
+        value::BorrowedStrDeserializer, DeserializeSeed, Deserializer, Error, IntoDeserializer,
+        MapAccess, SeqAccess, Unexpected, Visitor,
+    },
+    Deserialize, Serialize, Serializer,
+};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap, HashSet},
+    hash::Hash,
+    marker::PhantomData,
+};
+
+/// Reports a validation failure the same way the serde::Deserialize impls generated by the derives do, i.e. naming
+/// the type and what it expects (see [Describe::expecting]), or otherwise the validation error.
+fn invalid_value<T: Describe + ?Sized, E: Error>(v: &str, err: impl std::fmt::Display) -> E {
+    invalid_value_of::<T, E>(Unexpected::Str(v), err)
+}
+
+/// Same as [invalid_value], but for when the input is only described by `unexpected`.
+fn invalid_value_of<T: Describe + ?Sized, E: Error>(
+    unexpected: Unexpected,
+    err: impl std::fmt::Display,
+) -> E {
+    let expected = match T::expecting() {
+        Some(expecting) => format!("a {} ({})", T::TYPE_NAME, expecting),
+        None => format!("a {} ({})", T::TYPE_NAME, err),
+    };
+    E::invalid_value(unexpected, &expected.as_str())
+}
+
+/// Reports that a valid string couldn't be borrowed, the same way the serde::Deserialize impls generated by the
+/// derives do.
+fn cant_borrow<E: Error>(v: &str) -> E {
+    E::custom(format_args!(
+        "can't borrow {:?} from the input (e.g. because it contains escape sequences); deserialize the owned PneuString instead",
+        v
+    ))
+}
+
+/// Deserializes a `Cow<'de, T>` for a [PneuStr] `T`, borrowing from the input if the deserializer allows it,
+/// and otherwise deserializing the owned [PneuString].  Validation is done in either case.
+pub struct CowSeed<T: ?Sized>(PhantomData<T>);
+
+impl<T: ?Sized> CowSeed<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: ?Sized> Clone for CowSeed<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for CowSeed<T> {}
+
+impl<T: ?Sized> Default for CowSeed<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'de, T> DeserializeSeed<'de> for CowSeed<T>
+where
+    T: PneuStr + Describe + ToOwned + ?Sized + 'de,
+    T::Owned: PneuString,
+{
+    type Value = Cow<'de, T>;
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de, T> Visitor<'de> for CowSeed<T>
+where
+    T: PneuStr + Describe + ToOwned + ?Sized + 'de,
+    T::Owned: PneuString,
+{
+    type Value = Cow<'de, T>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "a {}", T::TYPE_NAME)
+    }
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        T::new_ref(v)
+            .map(Cow::Borrowed)
+            .map_err(|err| invalid_value::<T, _>(v, err))
+    }
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        T::new_ref(v)
+            .map(|t| Cow::Owned(t.to_owned()))
+            .map_err(|err| invalid_value::<T, _>(v, err))
+    }
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        match <T as Validate>::validate(v.as_str()) {
+            Ok(()) => Ok(Cow::Owned(unsafe {
+                <T::Owned as PneuString>::new_unchecked(v)
+            })),
+            Err(err) => Err(invalid_value::<T, _>(v.as_str(), err)),
+        }
+    }
+}
+
+/// Deserializes a `&'de T` for a [PneuStr] `T`.  As with the derived serde::Deserialize for `&T`, this requires that
+/// the deserializer be able to lend out the string.
+pub struct BorrowedSeed<T: ?Sized>(PhantomData<T>);
+
+impl<T: ?Sized> BorrowedSeed<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: ?Sized> Clone for BorrowedSeed<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for BorrowedSeed<T> {}
+
+impl<T: ?Sized> Default for BorrowedSeed<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'de, T> DeserializeSeed<'de> for BorrowedSeed<T>
+where
+    T: PneuStr + Describe + ?Sized + 'de,
+{
+    type Value = &'de T;
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de, T> Visitor<'de> for BorrowedSeed<T>
+where
+    T: PneuStr + Describe + ?Sized + 'de,
+{
+    type Value = &'de T;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "a borrowed {}", T::TYPE_NAME)
+    }
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        T::new_ref(v).map_err(|err| invalid_value::<T, _>(v, err))
+    }
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Err(match <T as Validate>::validate(v) {
+            Ok(()) => cant_borrow(v),
+            Err(err) => invalid_value::<T, _>(v, err),
+        })
+    }
+}
+
+/// Deserializes a [PneuString] `T`.
+pub struct OwnedSeed<T>(PhantomData<fn() -> T>);
+
+impl<T> OwnedSeed<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Clone for OwnedSeed<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for OwnedSeed<T> {}
+
+impl<T> Default for OwnedSeed<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'de, T> DeserializeSeed<'de> for OwnedSeed<T>
+where
+    T: PneuString + Describe,
+{
+    type Value = T;
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_string(self)
+    }
+}
+
+impl<'de, T> Visitor<'de> for OwnedSeed<T>
+where
+    T: PneuString + Describe,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "a {}", T::TYPE_NAME)
+    }
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        self.visit_string(v.to_owned())
+    }
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        match <T::Borrowed as Validate>::validate(v.as_str()) {
+            Ok(()) => Ok(unsafe { T::new_unchecked(v) }),
+            Err(err) => Err(invalid_value::<T, _>(v.as_str(), err)),
+        }
+    }
+}
+
+/// Deserializes a [PneuString] `T`, validating it against the given runtime context (see [ValidateWith]) as well.
+pub struct ContextSeed<'c, T, Ctx: ?Sized> {
+    ctx: &'c Ctx,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<'c, T, Ctx: ?Sized> ContextSeed<'c, T, Ctx> {
+    pub fn new(ctx: &'c Ctx) -> Self {
+        Self {
+            ctx,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, Ctx: ?Sized> Clone for ContextSeed<'_, T, Ctx> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, Ctx: ?Sized> Copy for ContextSeed<'_, T, Ctx> {}
+
+impl<'de, T, Ctx> DeserializeSeed<'de> for ContextSeed<'_, T, Ctx>
+where
+    T: PneuString + Describe,
+    T::Borrowed: ValidateWith<Ctx>,
+    Ctx: ?Sized,
+{
+    type Value = T;
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_string(self)
+    }
+}
+
+impl<'de, T, Ctx> Visitor<'de> for ContextSeed<'_, T, Ctx>
+where
+    T: PneuString + Describe,
+    T::Borrowed: ValidateWith<Ctx>,
+    Ctx: ?Sized,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "a {}", T::TYPE_NAME)
+    }
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        T::try_from_with(v, self.ctx).map_err(|err| invalid_value::<T, _>(v, err))
+    }
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        match <T::Borrowed as Validate>::validate(v.as_str())
+            .and_then(|()| <T::Borrowed as ValidateWith<Ctx>>::validate_with(v.as_str(), self.ctx))
+        {
+            Ok(()) => Ok(unsafe { T::new_unchecked(v) }),
+            Err(err) => Err(invalid_value::<T, _>(v.as_str(), err)),
+        }
+    }
+}
+
+/// Deserializes a [PneuString] `T` using [PneuString::from_legacy], i.e. accepting legacy forms and canonicalizing
+/// them into the strict form (see [ValidateLenient]).
+pub struct LenientSeed<T>(PhantomData<fn() -> T>);
+
+impl<T> LenientSeed<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Clone for LenientSeed<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for LenientSeed<T> {}
+
+impl<T> Default for LenientSeed<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'de, T> DeserializeSeed<'de> for LenientSeed<T>
+where
+    T: PneuString + Describe,
+    T::Borrowed: ValidateLenient,
+{
+    type Value = T;
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_string(self)
+    }
+}
+
+impl<'de, T> Visitor<'de> for LenientSeed<T>
+where
+    T: PneuString + Describe,
+    T::Borrowed: ValidateLenient,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "a {}", T::TYPE_NAME)
+    }
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        T::from_legacy(v).map_err(|err| invalid_value::<T, _>(v, err))
+    }
+}
+
+/// Deserializes a [PneuString] `T` using [PneuString::try_normalize], i.e. normalizing the input before validating
+/// it (see [Normalize]).
+pub struct NormalizeSeed<T>(PhantomData<fn() -> T>);
+
+impl<T> NormalizeSeed<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Clone for NormalizeSeed<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for NormalizeSeed<T> {}
+
+impl<T> Default for NormalizeSeed<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'de, T> DeserializeSeed<'de> for NormalizeSeed<T>
+where
+    T: PneuString + Describe,
+    T::Borrowed: Normalize,
+{
+    type Value = T;
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_string(self)
+    }
+}
+
+impl<'de, T> Visitor<'de> for NormalizeSeed<T>
+where
+    T: PneuString + Describe,
+    T::Borrowed: Normalize,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "a {}", T::TYPE_NAME)
+    }
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        T::normalize_from(v).map_err(|err| invalid_value::<T, _>(v, err))
+    }
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        // try_normalize reuses v's allocation, so v is gone by the time the error is reported.
+        T::try_normalize(v)
+            .map_err(|err| invalid_value_of::<T, _>(Unexpected::Other("string"), err))
+    }
+}
+
+/// Deserializes a `&'de T` for a [PneuStr] `T`, validating it against the given runtime context (see
+/// [ValidateWith]) as well.  As with the derived serde::Deserialize for `&T`, this requires that the deserializer
+/// be able to lend out the string.
+pub struct BorrowedContextSeed<'c, T: ?Sized, Ctx: ?Sized> {
+    ctx: &'c Ctx,
+    phantom: PhantomData<T>,
+}
+
+impl<'c, T: ?Sized, Ctx: ?Sized> BorrowedContextSeed<'c, T, Ctx> {
+    pub fn new(ctx: &'c Ctx) -> Self {
+        Self {
+            ctx,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized, Ctx: ?Sized> Clone for BorrowedContextSeed<'_, T, Ctx> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized, Ctx: ?Sized> Copy for BorrowedContextSeed<'_, T, Ctx> {}
+
+impl<'de, T, Ctx> DeserializeSeed<'de> for BorrowedContextSeed<'_, T, Ctx>
+where
+    T: PneuStr + Describe + ValidateWith<Ctx> + ?Sized + 'de,
+    Ctx: ?Sized,
+{
+    type Value = &'de T;
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de, T, Ctx> Visitor<'de> for BorrowedContextSeed<'_, T, Ctx>
+where
+    T: PneuStr + Describe + ValidateWith<Ctx> + ?Sized + 'de,
+    Ctx: ?Sized,
+{
+    type Value = &'de T;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "a borrowed {}", T::TYPE_NAME)
+    }
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        T::new_ref_with(v, self.ctx).map_err(|err| invalid_value::<T, _>(v, err))
+    }
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Err(match T::new_ref_with(v, self.ctx) {
+            Ok(_) => cant_borrow(v),
+            Err(err) => invalid_value::<T, _>(v, err),
+        })
+    }
+}
+
+/// Deserializes an `Option` of whatever the given [DeserializeSeed] produces.
+#[derive(Clone, Copy, Default)]
+pub struct OptionSeed<S>(pub S);
+
+impl<'de, S> DeserializeSeed<'de> for OptionSeed<S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = Option<S::Value>;
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_option(self)
+    }
+}
+
+impl<'de, S> Visitor<'de> for OptionSeed<S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = Option<S::Value>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("an optional value")
+    }
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(None)
+    }
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(None)
+    }
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.0.deserialize(deserializer).map(Some)
+    }
+}
+
+/// Deserializes a `Vec` of whatever the given [DeserializeSeed] produces.
+#[derive(Clone, Copy, Default)]
+pub struct VecSeed<S>(pub S);
+
+impl<'de, S> DeserializeSeed<'de> for VecSeed<S>
+where
+    S: DeserializeSeed<'de> + Clone,
+{
+    type Value = Vec<S::Value>;
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, S> Visitor<'de> for VecSeed<S>
+where
+    S: DeserializeSeed<'de> + Clone,
+{
+    type Value = Vec<S::Value>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a sequence")
+    }
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        // Don't trust the size hint too far, same as serde does.
+        let mut v = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(element) = seq.next_element_seed(self.0.clone())? {
+            v.push(element);
+        }
+        Ok(v)
+    }
+}
+
+/// Deserializes a `HashSet` of whatever the given [DeserializeSeed] produces.
+#[derive(Clone, Copy, Default)]
+pub struct HashSetSeed<S>(pub S);
+
+impl<'de, S> DeserializeSeed<'de> for HashSetSeed<S>
+where
+    S: DeserializeSeed<'de> + Clone,
+    S::Value: Eq + Hash,
+{
+    type Value = HashSet<S::Value>;
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, S> Visitor<'de> for HashSetSeed<S>
+where
+    S: DeserializeSeed<'de> + Clone,
+    S::Value: Eq + Hash,
+{
+    type Value = HashSet<S::Value>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a sequence")
+    }
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut set = HashSet::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(element) = seq.next_element_seed(self.0.clone())? {
+            set.insert(element);
+        }
+        Ok(set)
+    }
+}
+
+/// Deserializes a map key with the given [DeserializeSeed], also accepting an integer key in its string form, since
+/// some formats (e.g. YAML) and serde's own map deserializers give integer-looking keys as integers.  Integers are
+/// only accepted here, i.e. as map keys, and not wherever the pneutype appears.  [HashMapSeed] and [BTreeMapSeed]
+/// use this for their keys.
+#[derive(Clone, Copy, Default)]
+pub struct MapKeySeed<S>(pub S);
+
+impl<'de, S> DeserializeSeed<'de> for MapKeySeed<S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = S::Value;
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de, S> Visitor<'de> for MapKeySeed<S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = S::Value;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a string or integer map key")
+    }
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        self.0.deserialize(BorrowedStrDeserializer::new(v))
+    }
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        self.0.deserialize(v.into_deserializer())
+    }
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        self.0.deserialize(v.into_deserializer())
+    }
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        self.visit_string(v.to_string())
+    }
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        self.visit_string(v.to_string())
+    }
+    fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        self.visit_string(v.to_string())
+    }
+    fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        self.visit_string(v.to_string())
+    }
+}
+
+/// Deserializes a `HashMap` whose keys and values are produced by the given [DeserializeSeed]s.  The keys are
+/// deserialized via [MapKeySeed].
+#[derive(Clone, Copy, Default)]
+pub struct HashMapSeed<K, V>(pub K, pub V);
+
+impl<'de, K, V> DeserializeSeed<'de> for HashMapSeed<K, V>
+where
+    K: DeserializeSeed<'de> + Clone,
+    K::Value: Eq + Hash,
+    V: DeserializeSeed<'de> + Clone,
+{
+    type Value = HashMap<K::Value, V::Value>;
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, K, V> Visitor<'de> for HashMapSeed<K, V>
+where
+    K: DeserializeSeed<'de> + Clone,
+    K::Value: Eq + Hash,
+    V: DeserializeSeed<'de> + Clone,
+{
+    type Value = HashMap<K::Value, V::Value>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a map")
+    }
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut m = HashMap::with_capacity(map.size_hint().unwrap_or(0).min(4096));
+        while let Some(key) = map.next_key_seed(MapKeySeed(self.0.clone()))? {
+            let value = map.next_value_seed(self.1.clone())?;
+            m.insert(key, value);
+        }
+        Ok(m)
+    }
+}
+
+/// Deserializes a `BTreeMap` whose keys and values are produced by the given [DeserializeSeed]s.  The keys are
+/// deserialized via [MapKeySeed].
+#[derive(Clone, Copy, Default)]
+pub struct BTreeMapSeed<K, V>(pub K, pub V);
+
+impl<'de, K, V> DeserializeSeed<'de> for BTreeMapSeed<K, V>
+where
+    K: DeserializeSeed<'de> + Clone,
+    K::Value: Ord,
+    V: DeserializeSeed<'de> + Clone,
+{
+    type Value = BTreeMap<K::Value, V::Value>;
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, K, V> Visitor<'de> for BTreeMapSeed<K, V>
+where
+    K: DeserializeSeed<'de> + Clone,
+    K::Value: Ord,
+    V: DeserializeSeed<'de> + Clone,
+{
+    type Value = BTreeMap<K::Value, V::Value>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a map")
+    }
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut m = BTreeMap::new();
+        while let Some(key) = map.next_key_seed(MapKeySeed(self.0.clone()))? {
+            let value = map.next_value_seed(self.1.clone())?;
+            m.insert(key, value);
+        }
+        Ok(m)
+    }
+}
+
+/// For use as `#[serde(borrow, with = "pneutype::serde::cow")]` on a field of type `Cow<'a, T>`, where `T` is a
+/// [PneuStr].  Borrows from the input where possible, see [CowSeed].
+pub mod cow {
+    use super::*;
+
+    pub fn deserialize<'de: 'a, 'a, D, T>(deserializer: D) -> Result<Cow<'a, T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: PneuStr + Describe + ToOwned + ?Sized + 'de,
+        T::Owned: PneuString,
+    {
+        CowSeed::new().deserialize(deserializer)
+    }
+
+    // serde passes a reference to the field itself, hence &Cow.
+    #[allow(clippy::ptr_arg)]
+    pub fn serialize<S, T>(value: &Cow<'_, T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: AsStr + ToOwned + ?Sized,
+    {
+        serializer.serialize_str(value.as_ref().as_str())
+    }
+}
+
+/// For use as `#[serde(borrow, default, with = "pneutype::serde::option_cow")]` on a field of type
+/// `Option<Cow<'a, T>>`, where `T` is a [PneuStr].  Note that `default` is needed in order for the field to be
+/// optional, as with any `with`-module.
+pub mod option_cow {
+    use super::*;
+
+    pub fn deserialize<'de: 'a, 'a, D, T>(deserializer: D) -> Result<Option<Cow<'a, T>>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: PneuStr + Describe + ToOwned + ?Sized + 'de,
+        T::Owned: PneuString,
+    {
+        OptionSeed(CowSeed::new()).deserialize(deserializer)
+    }
+
+    pub fn serialize<S, T>(value: &Option<Cow<'_, T>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: AsStr + ToOwned + ?Sized,
+    {
+        match value {
+            Some(value) => serializer.serialize_some(value.as_ref().as_str()),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+/// For use as `#[serde(borrow, with = "pneutype::serde::vec_cow")]` on a field of type `Vec<Cow<'a, T>>`, where
+/// `T` is a [PneuStr].
+pub mod vec_cow {
+    use super::*;
+
+    pub fn deserialize<'de: 'a, 'a, D, T>(deserializer: D) -> Result<Vec<Cow<'a, T>>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: PneuStr + Describe + ToOwned + ?Sized + 'de,
+        T::Owned: PneuString,
+    {
+        VecSeed(CowSeed::new()).deserialize(deserializer)
+    }
+
+    pub fn serialize<S, T>(value: &[Cow<'_, T>], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: AsStr + ToOwned + ?Sized,
+    {
+        serializer.collect_seq(value.iter().map(|element| element.as_ref().as_str()))
+    }
+}
+
+/// For use as `#[serde(borrow, with = "pneutype::serde::hash_set_cow")]` on a field of type
+/// `HashSet<Cow<'a, T>>`, where `T` is a [PneuStr].
+pub mod hash_set_cow {
+    use super::*;
+
+    pub fn deserialize<'de: 'a, 'a, D, T>(deserializer: D) -> Result<HashSet<Cow<'a, T>>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: PneuStr + Describe + ToOwned + Eq + Hash + ?Sized + 'de,
+        T::Owned: PneuString,
+    {
+        HashSetSeed(CowSeed::new()).deserialize(deserializer)
+    }
+
+    pub fn serialize<S, T>(value: &HashSet<Cow<'_, T>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: AsStr + ToOwned + ?Sized,
+    {
+        serializer.collect_seq(value.iter().map(|element| element.as_ref().as_str()))
+    }
+}
+
+/// For use as `#[serde(borrow, with = "pneutype::serde::borrowed")]` on a field of type `&'a T`, where `T` is a
+/// [PneuStr].  See [BorrowedSeed].
+pub mod borrowed {
+    use super::*;
+
+    pub fn deserialize<'de: 'a, 'a, D, T>(deserializer: D) -> Result<&'a T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: PneuStr + Describe + ?Sized + 'de,
+    {
+        BorrowedSeed::new().deserialize(deserializer)
+    }
+
+    pub fn serialize<S, T>(value: &&T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: AsStr + ?Sized,
+    {
+        serializer.serialize_str(value.as_str())
+    }
+}
+
+/// For use as `#[serde(borrow, default, with = "pneutype::serde::option_borrowed")]` on a field of type
+/// `Option<&'a T>`, where `T` is a [PneuStr].
+pub mod option_borrowed {
+    use super::*;
+
+    pub fn deserialize<'de: 'a, 'a, D, T>(deserializer: D) -> Result<Option<&'a T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: PneuStr + Describe + ?Sized + 'de,
+    {
+        OptionSeed(BorrowedSeed::new()).deserialize(deserializer)
+    }
+
+    pub fn serialize<S, T>(value: &Option<&T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: AsStr + ?Sized,
+    {
+        match value {
+            Some(value) => serializer.serialize_some(value.as_str()),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+/// For use as `#[serde(borrow, with = "pneutype::serde::vec_borrowed")]` on a field of type `Vec<&'a T>`, where
+/// `T` is a [PneuStr].
+pub mod vec_borrowed {
+    use super::*;
+
+    pub fn deserialize<'de: 'a, 'a, D, T>(deserializer: D) -> Result<Vec<&'a T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: PneuStr + Describe + ?Sized + 'de,
+    {
+        VecSeed(BorrowedSeed::new()).deserialize(deserializer)
+    }
+
+    pub fn serialize<S, T>(value: &[&T], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: AsStr + ?Sized,
+    {
+        serializer.collect_seq(value.iter().map(|element| element.as_str()))
+    }
+}
+
+/// For use as `#[serde(borrow, with = "pneutype::serde::hash_set_borrowed")]` on a field of type
+/// `HashSet<&'a T>`, where `T` is a [PneuStr].
+pub mod hash_set_borrowed {
+    use super::*;
+
+    pub fn deserialize<'de: 'a, 'a, D, T>(deserializer: D) -> Result<HashSet<&'a T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: PneuStr + Describe + Eq + Hash + ?Sized + 'de,
+    {
+        HashSetSeed(BorrowedSeed::new()).deserialize(deserializer)
+    }
+
+    pub fn serialize<S, T>(value: &HashSet<&T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: AsStr + ?Sized,
+    {
+        serializer.collect_seq(value.iter().map(|element| element.as_str()))
+    }
+}
+
+/// For use as `#[serde(with = "pneutype::serde::owned")]` on a field whose type is a [PneuString] `T`.  See
+/// [OwnedSeed].
+pub mod owned {
+    use super::*;
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: PneuString + Describe,
+    {
+        OwnedSeed::new().deserialize(deserializer)
+    }
+
+    pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: AsStr,
+    {
+        serializer.serialize_str(value.as_str())
+    }
+}
+
+/// For use as `#[serde(default, with = "pneutype::serde::option_owned")]` on a field of type `Option<T>`, where
+/// `T` is a [PneuString].
+pub mod option_owned {
+    use super::*;
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: PneuString + Describe,
+    {
+        OptionSeed(OwnedSeed::new()).deserialize(deserializer)
+    }
+
+    pub fn serialize<S, T>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: AsStr,
+    {
+        match value {
+            Some(value) => serializer.serialize_some(value.as_str()),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+/// For use as `#[serde(with = "pneutype::serde::vec_owned")]` on a field of type `Vec<T>`, where `T` is a
+/// [PneuString].
+pub mod vec_owned {
+    use super::*;
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: PneuString + Describe,
+    {
+        VecSeed(OwnedSeed::new()).deserialize(deserializer)
+    }
+
+    pub fn serialize<S, T>(value: &[T], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: AsStr,
+    {
+        serializer.collect_seq(value.iter().map(|element| element.as_str()))
+    }
+}
+
+/// For use as `#[serde(with = "pneutype::serde::hash_set_owned")]` on a field of type `HashSet<T>`, where `T` is
+/// a [PneuString].
+pub mod hash_set_owned {
+    use super::*;
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<HashSet<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: PneuString + Describe + Eq + Hash,
+    {
+        HashSetSeed(OwnedSeed::new()).deserialize(deserializer)
+    }
+
+    pub fn serialize<S, T>(value: &HashSet<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: AsStr,
+    {
+        serializer.collect_seq(value.iter().map(|element| element.as_str()))
+    }
+}
+
+/// For use as `#[serde(with = "pneutype::serde::hash_map_owned")]` on a field of type `HashMap<K, V>`, where `K`
+/// is a [PneuString].  Integer keys are accepted in their string form, see [MapKeySeed].
+pub mod hash_map_owned {
+    use super::*;
+
+    pub fn deserialize<'de, D, K, V>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+    where
+        D: Deserializer<'de>,
+        K: PneuString + Describe + Eq + Hash,
+        V: Deserialize<'de>,
+    {
+        HashMapSeed(OwnedSeed::new(), PhantomData).deserialize(deserializer)
+    }
+
+    pub fn serialize<S, K, V>(value: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        K: AsStr,
+        V: Serialize,
+    {
+        serializer.collect_map(value.iter().map(|(key, value)| (key.as_str(), value)))
+    }
+}
+
+/// For use as `#[serde(with = "pneutype::serde::btree_map_owned")]` on a field of type `BTreeMap<K, V>`, where `K`
+/// is a [PneuString].  Integer keys are accepted in their string form, see [MapKeySeed].
+pub mod btree_map_owned {
+    use super::*;
+
+    pub fn deserialize<'de, D, K, V>(deserializer: D) -> Result<BTreeMap<K, V>, D::Error>
+    where
+        D: Deserializer<'de>,
+        K: PneuString + Describe + Ord,
+        V: Deserialize<'de>,
+    {
+        BTreeMapSeed(OwnedSeed::new(), PhantomData).deserialize(deserializer)
+    }
+
+    pub fn serialize<S, K, V>(value: &BTreeMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        K: AsStr,
+        V: Serialize,
+    {
+        serializer.collect_map(value.iter().map(|(key, value)| (key.as_str(), value)))
+    }
+}
+
+/// For use as `#[serde(with = "pneutype::serde::lenient")]` on a field whose type is a [PneuString] `T` whose
+/// PneuStr implements [ValidateLenient].  Legacy forms are canonicalized upon load, see [LenientSeed].
+pub mod lenient {
+    use super::*;
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: PneuString + Describe,
+        T::Borrowed: ValidateLenient,
+    {
+        LenientSeed::new().deserialize(deserializer)
+    }
+
+    pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: AsStr,
+    {
+        serializer.serialize_str(value.as_str())
+    }
+}
+
+/// For use as `#[serde(with = "pneutype::serde::normalize")]` on a field whose type is a [PneuString] `T` whose
+/// PneuStr implements [Normalize].  The input is normalized before it's validated, see [NormalizeSeed].
+pub mod normalize {
+    use super::*;
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: PneuString + Describe,
+        T::Borrowed: Normalize,
+    {
+        NormalizeSeed::new().deserialize(deserializer)
+    }
+
+    pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: AsStr,
+    {
+        serializer.serialize_str(value.as_str())
+    }
+}
@@ -0,0 +1,472 @@
+//! A conformance test kit for pneutypes, checking the properties that every [PneuString] (and its [PneuStr])
+//! should have, so that they don't have to be tested by hand for each pneutype.  The
+//! [pneutype_conformance!](crate::pneutype_conformance!) macro generates a module of tests for a [PneuString] from
+//! lists of valid and invalid strings:
+//! ```
+//! #[derive(Debug, Eq, Hash, PartialEq, pneutype::PneuString)]
+//! #[pneu_string(borrow = "LowercaseStr")]
+//! # #[pneu_string(no_arbitrary)]
+//! pub struct Lowercase(String);
+//!
+//! #[derive(Debug, Eq, Hash, PartialEq, pneutype::PneuStr)]
+//! #[repr(transparent)]
+//! pub struct LowercaseStr(str);
+//!
+//! impl pneutype::Validate for LowercaseStr {
+//!     type Data = str;
+//!     type Error = &'static str;
+//!     fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+//!         if data.chars().all(|c| c.is_ascii_lowercase()) {
+//!             Ok(())
+//!         } else {
+//!             Err("LowercaseStr must be all-lowercase ASCII")
+//!         }
+//!     }
+//! }
+//!
+//! pneutype::pneutype_conformance!(
+//!     lowercase_conformance: Lowercase,
+//!     valid = ["", "abc"],
+//!     invalid = ["Abc", "a b"],
+//!     std_impls,
+//!     hash,
+//! );
+//! ```
+//!
+//! The check functions that the generated tests call can also be used directly.  This module requires the
+//! `testing` feature, which is meant to be enabled only in `[dev-dependencies]`, e.g.
+//! ```toml
+//! [dev-dependencies]
+//! pneutype = { version = "...", features = ["testing"] }
+//! ```
+
+use crate::{AsStr, NewRefUnchecked, PneuStr, PneuString, PneuStringExt};
+
+/// Generates a module with the given name containing a test for each conformance check of the given [PneuString]
+/// type (see the [testing](crate::testing) module), each of which runs over all of the given valid and invalid
+/// strings.  The tests always include:
+/// -   `new_ref_agrees_with_try_from` (see [check_new_ref_agrees_with_try_from](crate::testing::check_new_ref_agrees_with_try_from))
+/// -   `to_owned_round_trips` (see [check_to_owned_round_trips](crate::testing::check_to_owned_round_trips))
+/// -   `as_ref_matches_as_str` (see [check_as_ref_matches_as_str](crate::testing::check_as_ref_matches_as_str))
+/// -   `new_unchecked_debug_asserts` (see [check_new_unchecked_debug_asserts](crate::testing::check_new_unchecked_debug_asserts))
+///
+/// and optionally, if named after the lists of strings:
+/// -   `std_impls` -- `from_str_agrees_with_try_from` and `deref_matches_as_str` (see
+///     [check_from_str_agrees_with_try_from](crate::testing::check_from_str_agrees_with_try_from) and
+///     [check_deref_matches_as_str](crate::testing::check_deref_matches_as_str)), which require the std impls of
+///     [PneuStringExt](crate::PneuStringExt), i.e. that none of `no_deref`, `no_display` or `no_from_str` was given.
+/// -   `hash` -- `borrow_hash_is_consistent` (see [check_borrow_hash_is_consistent](crate::testing::check_borrow_hash_is_consistent)),
+///     which requires [Hash](std::hash::Hash) and [Eq] for the [PneuString] and its [PneuStr].
+/// -   `serde` -- `serde_round_trips` (see [check_serde_round_trips](crate::testing::check_serde_round_trips)),
+///     which requires the `serde` feature.
+/// -   `serde_borrowed` -- `serde_borrowed_round_trips` (see
+///     [check_serde_borrowed_round_trips](crate::testing::check_serde_borrowed_round_trips)), which requires the
+///     `serde` feature.
+#[macro_export]
+macro_rules! pneutype_conformance {
+    (
+        $module:ident: $t:ty,
+        valid = [$($valid:expr),* $(,)?],
+        invalid = [$($invalid:expr),* $(,)?]
+        $(, $check:ident)* $(,)?
+    ) => {
+        #[cfg(test)]
+        mod $module {
+            #[allow(unused_imports)]
+            use super::*;
+
+            const VALID: &[&str] = &[$($valid),*];
+            const INVALID: &[&str] = &[$($invalid),*];
+
+            #[test]
+            fn new_ref_agrees_with_try_from() {
+                $crate::testing::check_new_ref_agrees_with_try_from::<$t>(VALID, INVALID);
+            }
+
+            #[test]
+            fn to_owned_round_trips() {
+                $crate::testing::check_to_owned_round_trips::<$t>(VALID);
+            }
+
+            #[test]
+            fn as_ref_matches_as_str() {
+                $crate::testing::check_as_ref_matches_as_str::<$t>(VALID);
+            }
+
+            #[test]
+            fn new_unchecked_debug_asserts() {
+                $crate::testing::check_new_unchecked_debug_asserts::<$t>(INVALID);
+            }
+
+            $($crate::pneutype_conformance!(@check $check, $t);)*
+        }
+    };
+    (@check std_impls, $t:ty) => {
+        #[test]
+        fn from_str_agrees_with_try_from() {
+            $crate::testing::check_from_str_agrees_with_try_from::<$t>(VALID, INVALID);
+        }
+
+        #[test]
+        fn deref_matches_as_str() {
+            $crate::testing::check_deref_matches_as_str::<$t>(VALID);
+        }
+    };
+    (@check hash, $t:ty) => {
+        #[test]
+        fn borrow_hash_is_consistent() {
+            $crate::testing::check_borrow_hash_is_consistent::<$t>(VALID);
+        }
+    };
+    (@check serde, $t:ty) => {
+        #[test]
+        fn serde_round_trips() {
+            $crate::testing::check_serde_round_trips::<$t>(VALID, INVALID);
+        }
+    };
+    (@check serde_borrowed, $t:ty) => {
+        #[test]
+        fn serde_borrowed_round_trips() {
+            $crate::testing::check_serde_borrowed_round_trips::<$t>(VALID, INVALID);
+        }
+    };
+}
+
+/// Checks that `new_ref` of the [PneuStr] and [TryFrom]<[String]> of the [PneuString] both accept the valid strings
+/// (preserving them exactly) and reject the invalid ones with the same error.
+pub fn check_new_ref_agrees_with_try_from<T>(valid: &[&str], invalid: &[&str])
+where
+    T: PneuString + std::fmt::Debug,
+    T::Borrowed: std::fmt::Debug,
+{
+    for &s in valid {
+        let t_ref = T::Borrowed::new_ref(s)
+            .unwrap_or_else(|err| panic!("new_ref rejected valid {:?}: {}", s, err));
+        assert_eq!(t_ref.as_str(), s);
+        let t = T::try_from(s.to_string())
+            .unwrap_or_else(|err| panic!("try_from rejected valid {:?}: {}", s, err));
+        assert_eq!(t.as_str(), s);
+    }
+    for &s in invalid {
+        let new_ref_err = match T::Borrowed::new_ref(s) {
+            Ok(t_ref) => panic!("new_ref accepted invalid {:?} as {:?}", s, t_ref),
+            Err(err) => err.to_string(),
+        };
+        let try_from_err = match T::try_from(s.to_string()) {
+            Ok(t) => panic!("try_from accepted invalid {:?} as {:?}", s, t),
+            Err(err) => err.to_string(),
+        };
+        // A generated error type (see the `error` attribute) also names the type and input, but still contains
+        // the validation error.
+        assert!(
+            try_from_err.contains(&new_ref_err),
+            "try_from error {:?} disagrees with new_ref error {:?}",
+            try_from_err,
+            new_ref_err
+        );
+    }
+}
+
+/// Checks that [FromStr](std::str::FromStr) of the [PneuString] accepts the valid strings (preserving them exactly)
+/// and rejects the invalid ones with the same error as [TryFrom]<[String]>.
+pub fn check_from_str_agrees_with_try_from<T>(valid: &[&str], invalid: &[&str])
+where
+    T: PneuStringExt + std::fmt::Debug,
+{
+    for &s in valid {
+        let t = s
+            .parse::<T>()
+            .unwrap_or_else(|err| panic!("from_str rejected valid {:?}: {}", s, err));
+        assert_eq!(t.as_str(), s);
+    }
+    for &s in invalid {
+        let from_str_err = match s.parse::<T>() {
+            Ok(t) => panic!("from_str accepted invalid {:?} as {:?}", s, t),
+            Err(err) => err.to_string(),
+        };
+        let try_from_err = match T::try_from(s.to_string()) {
+            Ok(t) => panic!("try_from accepted invalid {:?} as {:?}", s, t),
+            Err(err) => err.to_string(),
+        };
+        assert_eq!(
+            from_str_err, try_from_err,
+            "from_str error disagrees with try_from error"
+        );
+    }
+}
+
+/// Checks that [ToOwned] of the [PneuStr] gives the equivalent [PneuString], and that `as_pneu_str` and
+/// `into_string` give back the same string.
+pub fn check_to_owned_round_trips<T>(valid: &[&str])
+where
+    T: PneuString + std::fmt::Debug,
+    T::Borrowed: std::fmt::Debug + ToOwned<Owned = T>,
+{
+    for &s in valid {
+        let t_ref = T::Borrowed::new_ref(s).expect("pass");
+        let t = t_ref.to_owned();
+        assert_eq!(t.as_str(), s);
+        assert_eq!(t.as_pneu_str().as_str(), s);
+        assert_eq!(t.as_pneu_str().to_owned().as_str(), s);
+        assert_eq!(t.into_string(), s);
+    }
+}
+
+/// Checks that [AsRef] and [Borrow](std::borrow::Borrow) of the [PneuString] and its [PneuStr] all agree with
+/// `as_str`.
+pub fn check_as_ref_matches_as_str<T>(valid: &[&str])
+where
+    T: PneuString,
+{
+    use std::borrow::Borrow;
+    for &s in valid {
+        let t = T::try_from(s.to_string()).expect("pass");
+        assert_eq!(t.as_str(), s);
+        assert_eq!(<T as AsRef<str>>::as_ref(&t), s);
+        assert_eq!(<T as AsRef<T::Borrowed>>::as_ref(&t).as_str(), s);
+        assert_eq!(<T as Borrow<str>>::borrow(&t), s);
+        assert_eq!(<T as Borrow<T::Borrowed>>::borrow(&t).as_str(), s);
+        let t_ref = t.as_pneu_str();
+        assert_eq!(<T::Borrowed as AsRef<str>>::as_ref(t_ref), s);
+        assert_eq!(<T::Borrowed as Borrow<str>>::borrow(t_ref), s);
+    }
+}
+
+/// Checks that [Deref](std::ops::Deref) and [Display](std::fmt::Display) of the [PneuString] (and the latter of its
+/// [PneuStr]) agree with `as_str`.
+pub fn check_deref_matches_as_str<T>(valid: &[&str])
+where
+    T: PneuStringExt,
+    T::Borrowed: std::fmt::Display,
+{
+    for &s in valid {
+        let t = T::try_from(s.to_string()).expect("pass");
+        assert_eq!((*t).as_str(), s);
+        assert_eq!(t.to_string(), s);
+        let t_ref: &T::Borrowed = &t;
+        assert_eq!(t_ref.as_str(), s);
+        assert_eq!(t_ref.to_string(), s);
+    }
+}
+
+/// Checks that [Hash](std::hash::Hash) and [Eq] of the [PneuString] and its [PneuStr] are consistent with those of
+/// the `str` they [Borrow](std::borrow::Borrow), as the latter requires, so that e.g. a `HashSet<T>` can be looked up
+/// by `&str`.
+pub fn check_borrow_hash_is_consistent<T>(valid: &[&str])
+where
+    T: PneuString + Eq + std::hash::Hash,
+    T::Borrowed: Eq + std::hash::Hash,
+{
+    fn hash<H: std::hash::Hash + ?Sized>(h: &H) -> u64 {
+        use std::hash::{BuildHasher, BuildHasherDefault};
+        BuildHasherDefault::<std::collections::hash_map::DefaultHasher>::default().hash_one(h)
+    }
+    let set = valid
+        .iter()
+        .map(|&s| T::try_from(s.to_string()).expect("pass"))
+        .collect::<std::collections::HashSet<_>>();
+    for &s in valid {
+        let t = T::try_from(s.to_string()).expect("pass");
+        assert_eq!(
+            hash(&t),
+            hash(s),
+            "Hash of {:?} disagrees with that of str",
+            s
+        );
+        assert_eq!(
+            hash(t.as_pneu_str()),
+            hash(s),
+            "Hash of {:?} as PneuStr disagrees with that of str",
+            s
+        );
+        assert!(set.contains(s), "{:?} couldn't be looked up by str", s);
+        for &other in valid {
+            let other_t = T::try_from(other.to_string()).expect("pass");
+            assert_eq!(
+                t == other_t,
+                s == other,
+                "Eq of {:?} and {:?} disagrees with that of str",
+                s,
+                other
+            );
+            assert_eq!(
+                t.as_pneu_str() == other_t.as_pneu_str(),
+                s == other,
+                "Eq of {:?} and {:?} as PneuStr disagrees with that of str",
+                s,
+                other
+            );
+        }
+    }
+}
+
+/// Checks that [new_unchecked](PneuString::new_unchecked) and
+/// [new_ref_unchecked](crate::NewRefUnchecked::new_ref_unchecked) panic on invalid strings when debug assertions are
+/// enabled (and does nothing otherwise).
+pub fn check_new_unchecked_debug_asserts<T>(invalid: &[&str])
+where
+    T: PneuString,
+{
+    if !cfg!(debug_assertions) {
+        return;
+    }
+    for &s in invalid {
+        let result = std::panic::catch_unwind(|| {
+            let _ = unsafe { T::new_unchecked(s.to_string()) };
+        });
+        assert!(
+            result.is_err(),
+            "new_unchecked didn't debug-assert on invalid {:?}",
+            s
+        );
+        let result = std::panic::catch_unwind(|| {
+            let _ = unsafe { T::Borrowed::new_ref_unchecked(s) };
+        });
+        assert!(
+            result.is_err(),
+            "new_ref_unchecked didn't debug-assert on invalid {:?}",
+            s
+        );
+    }
+}
+
+/// Checks that the [PneuString] serializes as its string, deserializes back into the equivalent value, and fails to
+/// deserialize from the invalid strings.  Requires the `serde` feature.
+#[cfg(feature = "serde")]
+pub fn check_serde_round_trips<T>(valid: &[&str], invalid: &[&str])
+where
+    T: PneuString + std::fmt::Debug + serde::Serialize + serde::de::DeserializeOwned,
+{
+    use serde::de::{value::StrDeserializer, IntoDeserializer};
+    for &s in valid {
+        let t = T::try_from(s.to_string()).expect("pass");
+        assert_eq!(t.serialize(StrSerializer).expect("pass"), s);
+        let deserializer: StrDeserializer<'_, serde::de::value::Error> = s.into_deserializer();
+        let t = T::deserialize(deserializer)
+            .unwrap_or_else(|err| panic!("deserialize rejected valid {:?}: {}", s, err));
+        assert_eq!(t.as_str(), s);
+    }
+    for &s in invalid {
+        let deserializer: StrDeserializer<'_, serde::de::value::Error> = s.into_deserializer();
+        if let Ok(t) = T::deserialize(deserializer) {
+            panic!("deserialize accepted invalid {:?} as {:?}", s, t);
+        }
+    }
+}
+
+/// Same as [check_serde_round_trips], but for the borrowed [PneuStr] of the [PneuString], which is deserialized
+/// from a borrowed string.  Requires the `serde` feature.
+#[cfg(feature = "serde")]
+pub fn check_serde_borrowed_round_trips<T>(valid: &[&str], invalid: &[&str])
+where
+    T: PneuString,
+    T::Borrowed: std::fmt::Debug + serde::Serialize,
+    for<'de> &'de T::Borrowed: serde::Deserialize<'de>,
+{
+    use serde::{de::value::BorrowedStrDeserializer, Serialize};
+    for &s in valid {
+        let t_ref = T::Borrowed::new_ref(s).expect("pass");
+        assert_eq!(t_ref.serialize(StrSerializer).expect("pass"), s);
+        let deserializer = BorrowedStrDeserializer::<serde::de::value::Error>::new(s);
+        let t_ref = <&T::Borrowed as serde::Deserialize>::deserialize(deserializer)
+            .unwrap_or_else(|err| panic!("deserialize rejected valid {:?}: {}", s, err));
+        assert_eq!(t_ref.as_str(), s);
+    }
+    for &s in invalid {
+        let deserializer = BorrowedStrDeserializer::<serde::de::value::Error>::new(s);
+        if let Ok(t_ref) = <&T::Borrowed as serde::Deserialize>::deserialize(deserializer) {
+            panic!("deserialize accepted invalid {:?} as {:?}", s, t_ref);
+        }
+    }
+}
+
+/// A serializer which only accepts strings (possibly wrapped in newtype structs), producing them as-is, so that
+/// a pneutype's serialization can be checked without depending on a particular data format.
+#[cfg(feature = "serde")]
+struct StrSerializer;
+
+/// Implements the methods of [serde::Serializer] for everything but strings, as errors.
+#[cfg(feature = "serde")]
+macro_rules! unsupported {
+    ($($method:ident($($arg:ty),*) -> $ok:ty;)*) => {
+        $(
+            fn $method(self, $(_: $arg),*) -> Result<$ok, Self::Error> {
+                Err(serde::ser::Error::custom(concat!("expected a string, but got ", stringify!($method))))
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serializer for StrSerializer {
+    type Ok = String;
+    type Error = serde::de::value::Error;
+    type SerializeSeq = serde::ser::Impossible<String, Self::Error>;
+    type SerializeTuple = serde::ser::Impossible<String, Self::Error>;
+    type SerializeTupleStruct = serde::ser::Impossible<String, Self::Error>;
+    type SerializeTupleVariant = serde::ser::Impossible<String, Self::Error>;
+    type SerializeMap = serde::ser::Impossible<String, Self::Error>;
+    type SerializeStruct = serde::ser::Impossible<String, Self::Error>;
+    type SerializeStructVariant = serde::ser::Impossible<String, Self::Error>;
+    fn serialize_str(self, v: &str) -> Result<String, Self::Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        value: &V,
+    ) -> Result<String, Self::Error>
+    where
+        V: serde::Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+    fn serialize_some<V>(self, _value: &V) -> Result<String, Self::Error>
+    where
+        V: serde::Serialize + ?Sized,
+    {
+        Err(serde::ser::Error::custom(
+            "expected a string, but got serialize_some",
+        ))
+    }
+    fn serialize_newtype_variant<V>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &V,
+    ) -> Result<String, Self::Error>
+    where
+        V: serde::Serialize + ?Sized,
+    {
+        Err(serde::ser::Error::custom(
+            "expected a string, but got serialize_newtype_variant",
+        ))
+    }
+    unsupported! {
+        serialize_bool(bool) -> String;
+        serialize_i8(i8) -> String;
+        serialize_i16(i16) -> String;
+        serialize_i32(i32) -> String;
+        serialize_i64(i64) -> String;
+        serialize_u8(u8) -> String;
+        serialize_u16(u16) -> String;
+        serialize_u32(u32) -> String;
+        serialize_u64(u64) -> String;
+        serialize_f32(f32) -> String;
+        serialize_f64(f64) -> String;
+        serialize_char(char) -> String;
+        serialize_bytes(&[u8]) -> String;
+        serialize_none() -> String;
+        serialize_unit() -> String;
+        serialize_unit_struct(&'static str) -> String;
+        serialize_unit_variant(&'static str, u32, &'static str) -> String;
+        serialize_seq(Option<usize>) -> Self::SerializeSeq;
+        serialize_tuple(usize) -> Self::SerializeTuple;
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct;
+        serialize_tuple_variant(&'static str, u32, &'static str, usize) -> Self::SerializeTupleVariant;
+        serialize_map(Option<usize>) -> Self::SerializeMap;
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct;
+        serialize_struct_variant(&'static str, u32, &'static str, usize) -> Self::SerializeStructVariant;
+    }
+}
@@ -5,6 +5,12 @@ pub trait Validate {
     type Data: ?Sized;
     type Error: std::fmt::Debug + std::fmt::Display;
     fn validate(data: &Self::Data) -> Result<(), Self::Error>;
+    /// Validate the given data, reporting every failure instead of just the first, e.g. for form input or config
+    /// linting.  The default implementation just reports the failure of [Validate::validate], so validators that
+    /// can find several failures should implement this as well.  This must fail iff [Validate::validate] fails.
+    fn validate_all(data: &Self::Data) -> Result<(), Vec<Self::Error>> {
+        Self::validate(data).map_err(|err| vec![err])
+    }
 }
 
 /// Canonical implementation of Validate for str that never fails.
@@ -24,4 +30,7 @@ impl<T: PneuString> Validate for T {
     fn validate(data: &Self::Data) -> Result<(), Self::Error> {
         <<T as PneuString>::Borrowed as Validate>::validate(data)
     }
+    fn validate_all(data: &Self::Data) -> Result<(), Vec<Self::Error>> {
+        <<T as PneuString>::Borrowed as Validate>::validate_all(data)
+    }
 }
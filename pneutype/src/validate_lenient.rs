@@ -0,0 +1,30 @@
+use crate::{PneuString, Validate};
+use std::borrow::Cow;
+
+/// Used to declare a lenient validation mode which accepts legacy forms of the data (e.g. data that was written
+/// before the validation constraint defined by [Validate] was tightened), together with a canonicalization that
+/// maps those legacy forms into the strict form.  This is used by the `from_legacy` constructor of a PneuString and
+/// by its derived serde::Deserialize under `deserialize = "lenient"`, so that legacy data is upgraded upon load.
+/// Validation itself stays strict everywhere else.
+pub trait ValidateLenient: Validate<Data = str> {
+    /// Validate the given data in lenient mode.  Anything accepted by [Validate::validate] should also be accepted
+    /// by this.
+    fn validate_lenient(data: &str) -> Result<(), Self::Error>;
+    /// Map data accepted by [ValidateLenient::validate_lenient] into the strict form, which must be accepted by
+    /// [Validate::validate].  Data already in the strict form should be returned as [Cow::Borrowed], so that no
+    /// allocation is needed in the common case.
+    fn canonicalize(data: &str) -> Cow<'_, str>;
+}
+
+/// A PneuString inherits its PneuStr's impl of ValidateLenient.
+impl<T: PneuString> ValidateLenient for T
+where
+    <T as PneuString>::Borrowed: ValidateLenient,
+{
+    fn validate_lenient(data: &str) -> Result<(), Self::Error> {
+        <<T as PneuString>::Borrowed as ValidateLenient>::validate_lenient(data)
+    }
+    fn canonicalize(data: &str) -> Cow<'_, str> {
+        <<T as PneuString>::Borrowed as ValidateLenient>::canonicalize(data)
+    }
+}
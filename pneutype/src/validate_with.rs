@@ -0,0 +1,22 @@
+use crate::{PneuString, Validate};
+
+/// Used to define a validation constraint that depends on runtime context, e.g. a max length from a config file
+/// or a tenant-specific allow-list, in addition to the context-independent constraint defined by [Validate].  The
+/// context-aware constructors (`new_ref_with`, `try_from_with`, and the seeds in `pneutype::serde`) always check
+/// [Validate] first, so `validate_with` only needs to check the context-dependent part of the constraint.
+///
+/// Note that the unchecked constructors can only check [Validate] in their debug_assert!, since they have no
+/// context.
+pub trait ValidateWith<Ctx: ?Sized>: Validate {
+    fn validate_with(data: &Self::Data, ctx: &Ctx) -> Result<(), Self::Error>;
+}
+
+/// A PneuString inherits its PneuStr's impls of ValidateWith.
+impl<T: PneuString, Ctx: ?Sized> ValidateWith<Ctx> for T
+where
+    <T as PneuString>::Borrowed: ValidateWith<Ctx>,
+{
+    fn validate_with(data: &Self::Data, ctx: &Ctx) -> Result<(), Self::Error> {
+        <<T as PneuString>::Borrowed as ValidateWith<Ctx>>::validate_with(data, ctx)
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{validators::Implies, Constraint, Describe, Sanitize, SanitizeReport, Validate};
+use std::{borrow::Cow, marker::PhantomData};
+
+/// A generic [PneuStr](crate::PneuStr) whose validation constraint is given by the validator `V`, typically
+/// assembled from the marker types in [validators](crate::validators), so that new pneutypes can be defined with
+/// a type alias and no derive.  See [ValidatedString] for the owned counterpart.
+#[derive(pneutype::PneuStr)]
+#[cfg_attr(feature = "serde", pneu_str(deserialize, serialize))]
+#[pneu_str(str_field = "1", no_describe)]
+#[repr(transparent)]
+pub struct ValidatedStr<V: 'static + Validate<Data = str>>(PhantomData<V>, str);
+
+/// The owned counterpart of [ValidatedStr].
+#[derive(pneutype::PneuString)]
+#[cfg_attr(feature = "serde", pneu_string(deserialize, serialize))]
+#[pneu_string(borrow = "ValidatedStr", string_field = "1", no_describe)]
+pub struct ValidatedString<V: 'static + Validate<Data = str>>(PhantomData<V>, String);
+
+impl<V: Validate<Data = str>> Validate for ValidatedStr<V> {
+    type Data = str;
+    type Error = V::Error;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        V::validate(data)
+    }
+    fn validate_all(data: &Self::Data) -> Result<(), Vec<Self::Error>> {
+        V::validate_all(data)
+    }
+}
+
+/// Inherits the repair strategy of the validator, if it has one.
+impl<V: Sanitize> Sanitize for ValidatedStr<V> {
+    const TRUNCATES: bool = V::TRUNCATES;
+    fn sanitize<'a>(data: &'a str, report: &mut SanitizeReport) -> Cow<'a, str> {
+        V::sanitize(data, report)
+    }
+}
+
+/// Describes itself by the constraint of the validator.
+impl<V: Validate<Data = str> + Describe> Describe for ValidatedStr<V> {
+    const TYPE_NAME: &'static str = "ValidatedStr";
+    const CONSTRAINT_IS_CUSTOM: bool = V::CONSTRAINT_IS_CUSTOM;
+    fn constraint() -> Constraint {
+        V::constraint()
+    }
+    fn generic_type_name() -> Cow<'static, str> {
+        Cow::Owned(format!("ValidatedStr_{}", V::generic_type_name()))
+    }
+}
+
+impl<V: Validate<Data = str> + Describe> Describe for ValidatedString<V> {
+    const TYPE_NAME: &'static str = "ValidatedString";
+    const CONSTRAINT_IS_CUSTOM: bool = V::CONSTRAINT_IS_CUSTOM;
+    fn constraint() -> Constraint {
+        V::constraint()
+    }
+    fn generic_type_name() -> Cow<'static, str> {
+        Cow::Owned(format!("ValidatedString_{}", V::generic_type_name()))
+    }
+}
+
+impl<V: Validate<Data = str>> ValidatedStr<V> {
+    /// Convert to a [ValidatedStr] of a validator implied by `V`, e.g. from `ValidatedStr<And<A, B>>` to
+    /// `ValidatedStr<A>`, without revalidating.
+    pub fn weaken<W, I>(&self) -> &ValidatedStr<W>
+    where
+        V: Implies<W, I>,
+        W: Validate<Data = str>,
+    {
+        // Safe because of the guarantee made by Implies.
+        unsafe { ValidatedStr::<W>::new_ref_unchecked(self.as_str()) }
+    }
+}
+
+impl<V: Validate<Data = str>> ValidatedString<V> {
+    /// Convert to a [ValidatedString] of a validator implied by `V`, e.g. from `ValidatedString<And<A, B>>` to
+    /// `ValidatedString<A>`, without revalidating or reallocating.
+    pub fn weaken<W, I>(self) -> ValidatedString<W>
+    where
+        V: Implies<W, I>,
+        W: Validate<Data = str>,
+    {
+        // Safe because of the guarantee made by Implies.
+        unsafe { ValidatedString::<W>::new_unchecked(self.1) }
+    }
+}
+
+// These are implemented by hand so as not to require the validator markers to implement them.
+
+impl<V: Validate<Data = str>> std::fmt::Debug for ValidatedStr<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<V: Validate<Data = str>> PartialEq for ValidatedStr<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<V: Validate<Data = str>> Eq for ValidatedStr<V> {}
+
+impl<V: Validate<Data = str>> PartialOrd for ValidatedStr<V> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<V: Validate<Data = str>> Ord for ValidatedStr<V> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl<V: Validate<Data = str>> std::hash::Hash for ValidatedStr<V> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl<V: Validate<Data = str>> std::fmt::Debug for ValidatedString<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<V: Validate<Data = str>> Clone for ValidatedString<V> {
+    fn clone(&self) -> Self {
+        Self(PhantomData, self.1.clone())
+    }
+}
+
+impl<V: Validate<Data = str>> PartialEq for ValidatedString<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<V: Validate<Data = str>> Eq for ValidatedString<V> {}
+
+impl<V: Validate<Data = str>> PartialOrd for ValidatedString<V> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<V: Validate<Data = str>> Ord for ValidatedString<V> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl<V: Validate<Data = str>> std::hash::Hash for ValidatedString<V> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
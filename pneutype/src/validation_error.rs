@@ -0,0 +1,122 @@
+use std::{borrow::Cow, ops::Range};
+
+/// A structured validation error, which validators can opt into using as their [Validate](crate::Validate)`::Error`
+/// type (instead of e.g. `&'static str`).  It carries the failing rule, the byte range of the offending part of
+/// the input, the offending char (if any), and a description of what was expected, so that e.g. a CLI can point
+/// at exactly what was wrong via [ValidationError::underline].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidationError {
+    /// Short name of the rule that failed, e.g. "chars" or "max_len".
+    pub rule: Cow<'static, str>,
+    /// Byte range of the offending part of the input.  It's empty if there's no particular offending part, e.g.
+    /// if the input is too short.
+    pub span: Range<usize>,
+    /// The offending char, if the failure was due to a single char.
+    pub found: Option<char>,
+    /// Human-readable description of what was expected, e.g. "ASCII lowercase".
+    pub expected: Cow<'static, str>,
+}
+
+impl ValidationError {
+    /// Construct an error for the given rule and expectation, which isn't attributed to any particular part of
+    /// the input.
+    pub fn new(rule: impl Into<Cow<'static, str>>, expected: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            rule: rule.into(),
+            span: 0..0,
+            found: None,
+            expected: expected.into(),
+        }
+    }
+    /// Attribute this error to the given byte range of the input.
+    pub fn with_span(mut self, span: Range<usize>) -> Self {
+        self.span = span;
+        self
+    }
+    /// Attribute this error to the char at the given byte offset of the input, which is rounded down to a char
+    /// boundary.  If there's no char there (i.e. at the end of the input), then the span is empty instead.
+    pub fn at_char(mut self, input: &str, position: usize) -> Self {
+        let position = floor_char_boundary(input, position);
+        match input[position..].chars().next() {
+            Some(c) => {
+                self.span = position..position + c.len_utf8();
+                self.found = Some(c);
+            }
+            None => {
+                self.span = position..position;
+                self.found = None;
+            }
+        }
+        self
+    }
+    /// Returns the input with the offending part underlined on the following line, e.g.
+    /// ```text
+    /// Foo Bar
+    ///    ^
+    /// ```
+    /// Columns are counted in chars.  An empty span is indicated by a single `^` at its position.  A span that
+    /// doesn't fall on char boundaries is widened to the chars it touches.
+    pub fn underline(&self, input: &str) -> String {
+        let start = floor_char_boundary(input, self.span.start);
+        let end = ceil_char_boundary(input, self.span.end.max(start));
+        let column = input[..start].chars().count();
+        let width = input[start..end].chars().count().max(1);
+        format!("{}\n{}{}", input, " ".repeat(column), "^".repeat(width))
+    }
+}
+
+/// The greatest char boundary of `s` that's at most `i`.
+fn floor_char_boundary(s: &str, i: usize) -> usize {
+    let mut i = i.min(s.len());
+    while !s.is_char_boundary(i) {
+        i -= 1;
+    }
+    i
+}
+
+/// The least char boundary of `s` that's at least `i` (or the end of `s`).
+fn ceil_char_boundary(s: &str, i: usize) -> usize {
+    let mut i = i.min(s.len());
+    while !s.is_char_boundary(i) {
+        i += 1;
+    }
+    i
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.found {
+            Some(c) => write!(
+                f,
+                "{}: expected {}, found {:?} at byte {}",
+                self.rule, self.expected, c, self.span.start
+            ),
+            None => write!(f, "{}: expected {}", self.rule, self.expected),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// With the `miette` feature, a [ValidationError] is a diagnostic whose code is the rule, whose help is what was
+/// expected, and whose label points at the offending part of the input.  The input itself isn't held by the error,
+/// so it has to be supplied as the source code, e.g. via the `diagnostic` attribute of the error type generated by
+/// the `error` attribute of [PneuString](crate::PneuString) and [PneuStr](crate::PneuStr).
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for ValidationError {
+    fn code<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        Some(Box::new(&self.rule))
+    }
+    fn help<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        Some(Box::new(format!("expected {}", self.expected)))
+    }
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        let label = match self.found {
+            Some(c) => format!("found {:?}", c),
+            None => "here".to_string(),
+        };
+        Some(Box::new(std::iter::once(
+            miette::LabeledSpan::new_primary_with_span(Some(label), self.span.clone()),
+        )))
+    }
+}
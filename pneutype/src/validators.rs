@@ -0,0 +1,784 @@
+//! Zero-sized validator marker types, for use as the parameter of [ValidatedStr](crate::ValidatedStr) and
+//! [ValidatedString](crate::ValidatedString).  Each implements [Validate], and they can be combined, so that new
+//! pneutypes can be assembled from type aliases without any derive:
+//! ```
+//! use pneutype::{
+//!     validators::{AsciiLowercase, And, CharsAll, MaxLen, MinLen},
+//!     ValidatedStr, ValidatedString,
+//! };
+//!
+//! type UsernameRule = And<MinLen<3>, And<MaxLen<16>, CharsAll<AsciiLowercase>>>;
+//! type UsernameStr = ValidatedStr<UsernameRule>;
+//! type Username = ValidatedString<UsernameRule>;
+//!
+//! let u = UsernameStr::new_ref("alice").expect("pass");
+//! assert!(UsernameStr::new_ref("al").is_err());
+//! assert!(UsernameStr::new_ref("Alice").is_err());
+//! // And<A, B> implies A (and B), so this conversion is free.
+//! let v: &ValidatedStr<CharsAll<AsciiLowercase>> = u.weaken();
+//! assert_eq!(v.as_str(), "alice");
+//! let u = Username::try_from("bob").expect("pass");
+//! assert_eq!(u.as_str(), "bob");
+//! ```
+
+use crate::{Constraint, Describe, Sanitize, SanitizeReport, Validate, ValidationError};
+use std::{borrow::Cow, marker::PhantomData};
+
+/// Valid iff both `A` and `B` are valid.  `A` is checked first.
+pub struct And<A, B>(PhantomData<(A, B)>);
+
+/// Valid iff either of `A` or `B` is valid.  `A` is checked first.
+pub struct Or<A, B>(PhantomData<(A, B)>);
+
+/// Valid iff `A` is not valid.
+pub struct Not<A>(PhantomData<A>);
+
+/// Valid iff the length in bytes is at most `N`.
+pub struct MaxLen<const N: usize>;
+
+/// Valid iff the length in bytes is at least `N`.
+pub struct MinLen<const N: usize>;
+
+/// Valid iff every char satisfies the [CharPredicate] `P`.
+pub struct CharsAll<P>(PhantomData<P>);
+
+/// A predicate on chars, for use in [CharsAll].
+pub trait CharPredicate {
+    /// Human-readable description of the chars that satisfy this predicate, for use in error messages.
+    fn description() -> Cow<'static, str>;
+    fn test(c: char) -> bool;
+    /// The chars that satisfy this predicate as the contents of a regex bracket expression (e.g. "a-z"), if they
+    /// can be expressed that way, for use in e.g. JSON schemas.
+    fn regex_class() -> Option<Cow<'static, str>> {
+        None
+    }
+    /// The name of this predicate, e.g. "AsciiLowercase", for naming the instantiations of generic types
+    /// parameterized by it (see [Describe::generic_type_name]).  By default this is the description with every run
+    /// of non-alphanumeric chars replaced by `_`.
+    fn name() -> Cow<'static, str> {
+        Cow::Owned(crate::describe::identifier(&Self::description()))
+    }
+}
+
+macro_rules! char_predicate {
+    ($(#[$attr:meta])* $name:ident, $description:literal, $regex_class:expr, $test:expr) => {
+        $(#[$attr])*
+        pub struct $name;
+
+        impl CharPredicate for $name {
+            fn description() -> Cow<'static, str> {
+                Cow::Borrowed($description)
+            }
+            fn test(c: char) -> bool {
+                $test(c)
+            }
+            fn regex_class() -> Option<Cow<'static, str>> {
+                $regex_class.map(Cow::Borrowed)
+            }
+            fn name() -> Cow<'static, str> {
+                Cow::Borrowed(stringify!($name))
+            }
+        }
+    };
+}
+
+char_predicate!(
+    /// Satisfied by ASCII chars.
+    Ascii,
+    "ASCII",
+    Some("\\x00-\\x7F"),
+    |c: char| c.is_ascii()
+);
+char_predicate!(
+    /// Satisfied by ASCII lowercase letters.
+    AsciiLowercase,
+    "ASCII lowercase",
+    Some("a-z"),
+    |c: char| c.is_ascii_lowercase()
+);
+char_predicate!(
+    /// Satisfied by ASCII uppercase letters.
+    AsciiUppercase,
+    "ASCII uppercase",
+    Some("A-Z"),
+    |c: char| c.is_ascii_uppercase()
+);
+char_predicate!(
+    /// Satisfied by ASCII digits.
+    AsciiDigit,
+    "ASCII digit",
+    Some("0-9"),
+    |c: char| c.is_ascii_digit()
+);
+char_predicate!(
+    /// Satisfied by ASCII letters and digits.
+    AsciiAlphanumeric,
+    "ASCII alphanumeric",
+    Some("0-9A-Za-z"),
+    |c: char| c.is_ascii_alphanumeric()
+);
+char_predicate!(
+    /// Satisfied by non-whitespace chars.
+    NonWhitespace,
+    "non-whitespace",
+    None::<&str>,
+    |c: char| !c.is_whitespace()
+);
+
+/// Satisfied by chars that satisfy both `A` and `B`.
+pub struct CharAnd<A, B>(PhantomData<(A, B)>);
+
+impl<A: CharPredicate, B: CharPredicate> CharPredicate for CharAnd<A, B> {
+    fn description() -> Cow<'static, str> {
+        Cow::Owned(format!("{} and {}", A::description(), B::description()))
+    }
+    fn test(c: char) -> bool {
+        A::test(c) && B::test(c)
+    }
+    fn name() -> Cow<'static, str> {
+        Cow::Owned(format!("CharAnd_{}_{}", A::name(), B::name()))
+    }
+}
+
+/// Satisfied by chars that satisfy either of `A` or `B`, e.g. `CharOr<AsciiLowercase, AsciiDigit>`.
+pub struct CharOr<A, B>(PhantomData<(A, B)>);
+
+impl<A: CharPredicate, B: CharPredicate> CharPredicate for CharOr<A, B> {
+    fn description() -> Cow<'static, str> {
+        Cow::Owned(format!("{} or {}", A::description(), B::description()))
+    }
+    fn test(c: char) -> bool {
+        A::test(c) || B::test(c)
+    }
+    fn regex_class() -> Option<Cow<'static, str>> {
+        Some(Cow::Owned(format!(
+            "{}{}",
+            A::regex_class()?,
+            B::regex_class()?
+        )))
+    }
+    fn name() -> Cow<'static, str> {
+        Cow::Owned(format!("CharOr_{}_{}", A::name(), B::name()))
+    }
+}
+
+/// Satisfied by the char `C`, e.g. `CharOr<AsciiLowercase, CharIs<'-'>>`.
+pub struct CharIs<const C: char>;
+
+impl<const C: char> CharPredicate for CharIs<C> {
+    fn description() -> Cow<'static, str> {
+        Cow::Owned(format!("{:?}", C))
+    }
+    fn test(c: char) -> bool {
+        c == C
+    }
+    fn regex_class() -> Option<Cow<'static, str>> {
+        // These are the chars that are special within a bracket expression.
+        if matches!(C, '\\' | ']' | '[' | '^' | '-') {
+            Some(Cow::Owned(format!("\\{}", C)))
+        } else {
+            Some(Cow::Owned(C.to_string()))
+        }
+    }
+    /// The char is given by its code point in hex, since it might not be alphanumeric.
+    fn name() -> Cow<'static, str> {
+        Cow::Owned(format!("CharIs_{:X}", C as u32))
+    }
+}
+
+/// The error for [And], which reports whichever side failed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AndError<EA, EB> {
+    Left(EA),
+    Right(EB),
+}
+
+impl<EA: std::fmt::Display, EB: std::fmt::Display> std::fmt::Display for AndError<EA, EB> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Left(err) => err.fmt(f),
+            Self::Right(err) => err.fmt(f),
+        }
+    }
+}
+
+impl<EA, EB> std::error::Error for AndError<EA, EB>
+where
+    EA: std::fmt::Debug + std::fmt::Display,
+    EB: std::fmt::Debug + std::fmt::Display,
+{
+}
+
+/// The error for [Or], which reports both sides.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrError<EA, EB> {
+    pub left: EA,
+    pub right: EB,
+}
+
+impl<EA: std::fmt::Display, EB: std::fmt::Display> std::fmt::Display for OrError<EA, EB> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}, and {}", self.left, self.right)
+    }
+}
+
+impl<EA, EB> std::error::Error for OrError<EA, EB>
+where
+    EA: std::fmt::Debug + std::fmt::Display,
+    EB: std::fmt::Debug + std::fmt::Display,
+{
+}
+
+/// The error for [Not].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NotError;
+
+impl std::fmt::Display for NotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("must not satisfy the negated constraint")
+    }
+}
+
+impl std::error::Error for NotError {}
+
+/// The error for [MinLen], [MaxLen] and [Bounded].  The lengths are in the given `unit`, which is the
+/// [LengthUnit::NAME] of the unit they were measured in, whereas `position` is always a byte offset.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LengthError {
+    TooShort {
+        min: usize,
+        actual: usize,
+        unit: &'static str,
+    },
+    TooLong {
+        max: usize,
+        actual: usize,
+        unit: &'static str,
+        /// The byte offset of the end of the longest prefix that isn't too long.
+        position: usize,
+    },
+}
+
+impl std::fmt::Display for LengthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooShort { min, actual, unit } => write!(
+                f,
+                "must be at least {} long, but was {}",
+                Length(*min, unit),
+                actual
+            ),
+            Self::TooLong {
+                max, actual, unit, ..
+            } => write!(
+                f,
+                "must be at most {} long, but was {}",
+                Length(*max, unit),
+                actual
+            ),
+        }
+    }
+}
+
+/// Displays a length along with its unit, e.g. "8 chars", or "1 char" (the unit's name is plural).
+struct Length<'a>(usize, &'a str);
+
+impl std::fmt::Display for Length<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self(len, unit) = *self;
+        match unit.strip_suffix('s') {
+            Some(singular) if len == 1 => write!(f, "{} {}", len, singular),
+            _ => write!(f, "{} {}", len, unit),
+        }
+    }
+}
+
+impl std::error::Error for LengthError {}
+
+/// The error for [CharsAll], which reports the first char that didn't satisfy the predicate.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidCharError {
+    /// The byte offset of the char.
+    pub position: usize,
+    pub c: char,
+    /// The [CharPredicate::description] of the predicate.
+    pub expected: Cow<'static, str>,
+}
+
+impl std::fmt::Display for InvalidCharError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid char {:?} at byte {} (expected {})",
+            self.c, self.position, self.expected
+        )
+    }
+}
+
+impl std::error::Error for InvalidCharError {}
+
+// Conversions of the errors into ValidationError, for validators that use it as their error type.
+
+impl<EA, EB> From<AndError<EA, EB>> for ValidationError
+where
+    EA: Into<ValidationError>,
+    EB: Into<ValidationError>,
+{
+    fn from(err: AndError<EA, EB>) -> Self {
+        match err {
+            AndError::Left(err) => err.into(),
+            AndError::Right(err) => err.into(),
+        }
+    }
+}
+
+impl From<NotError> for ValidationError {
+    fn from(_: NotError) -> Self {
+        ValidationError::new("not", "not to satisfy the negated constraint")
+    }
+}
+
+/// The span is empty, at the start of the input if it's too short, and at the end of the longest prefix that isn't
+/// too long if it's too long.
+impl From<LengthError> for ValidationError {
+    fn from(err: LengthError) -> Self {
+        match err {
+            LengthError::TooShort { min, actual, unit } => ValidationError::new(
+                "min_len",
+                format!(
+                    "a length of at least {} (was {})",
+                    Length(min, unit),
+                    actual
+                ),
+            ),
+            LengthError::TooLong {
+                max,
+                actual,
+                unit,
+                position,
+            } => ValidationError::new(
+                "max_len",
+                format!("a length of at most {} (was {})", Length(max, unit), actual),
+            )
+            .with_span(position..position),
+        }
+    }
+}
+
+impl From<InvalidCharError> for ValidationError {
+    fn from(err: InvalidCharError) -> Self {
+        ValidationError {
+            rule: "chars".into(),
+            span: err.position..err.position + err.c.len_utf8(),
+            found: Some(err.c),
+            expected: err.expected,
+        }
+    }
+}
+
+impl<A, B> Validate for And<A, B>
+where
+    A: Validate<Data = str>,
+    B: Validate<Data = str>,
+{
+    type Data = str;
+    type Error = AndError<A::Error, B::Error>;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        A::validate(data).map_err(AndError::Left)?;
+        B::validate(data).map_err(AndError::Right)
+    }
+    /// Reports the failures of both sides.
+    fn validate_all(data: &Self::Data) -> Result<(), Vec<Self::Error>> {
+        let mut errs = Vec::new();
+        if let Err(left_errs) = A::validate_all(data) {
+            errs.extend(left_errs.into_iter().map(AndError::Left));
+        }
+        if let Err(right_errs) = B::validate_all(data) {
+            errs.extend(right_errs.into_iter().map(AndError::Right));
+        }
+        if errs.is_empty() {
+            Ok(())
+        } else {
+            Err(errs)
+        }
+    }
+}
+
+impl<A, B> Validate for Or<A, B>
+where
+    A: Validate<Data = str>,
+    B: Validate<Data = str>,
+{
+    type Data = str;
+    type Error = OrError<A::Error, B::Error>;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        match A::validate(data) {
+            Ok(()) => Ok(()),
+            Err(left) => B::validate(data).map_err(|right| OrError { left, right }),
+        }
+    }
+}
+
+impl<A> Validate for Not<A>
+where
+    A: Validate<Data = str>,
+{
+    type Data = str;
+    type Error = NotError;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        match A::validate(data) {
+            Ok(()) => Err(NotError),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+impl<const N: usize> Validate for MaxLen<N> {
+    type Data = str;
+    type Error = LengthError;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        if data.len() > N {
+            Err(LengthError::TooLong {
+                max: N,
+                actual: data.len(),
+                unit: Bytes::NAME,
+                position: Bytes::truncate(data, N).len(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<const N: usize> Validate for MinLen<N> {
+    type Data = str;
+    type Error = LengthError;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        if data.len() < N {
+            Err(LengthError::TooShort {
+                min: N,
+                actual: data.len(),
+                unit: Bytes::NAME,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<P: CharPredicate> Validate for CharsAll<P> {
+    type Data = str;
+    type Error = InvalidCharError;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        match data.char_indices().find(|&(_, c)| !P::test(c)) {
+            Some((position, c)) => Err(InvalidCharError {
+                position,
+                c,
+                expected: P::description(),
+            }),
+            None => Ok(()),
+        }
+    }
+    /// Reports every char that doesn't satisfy the predicate.
+    fn validate_all(data: &Self::Data) -> Result<(), Vec<Self::Error>> {
+        let errs = data
+            .char_indices()
+            .filter(|&(_, c)| !P::test(c))
+            .map(|(position, c)| InvalidCharError {
+                position,
+                c,
+                expected: P::description(),
+            })
+            .collect::<Vec<_>>();
+        if errs.is_empty() {
+            Ok(())
+        } else {
+            Err(errs)
+        }
+    }
+}
+
+// Sanitize impls for the validators that have an obvious repair strategy.
+
+/// Truncates on a char boundary.
+impl<const N: usize> Sanitize for MaxLen<N> {
+    const TRUNCATES: bool = true;
+    fn sanitize<'a>(data: &'a str, report: &mut SanitizeReport) -> Cow<'a, str> {
+        report.truncate_bytes(data, N, "too long")
+    }
+}
+
+/// Removes the chars that don't satisfy the predicate.
+impl<P: CharPredicate> Sanitize for CharsAll<P> {
+    fn sanitize<'a>(data: &'a str, report: &mut SanitizeReport) -> Cow<'a, str> {
+        if data.chars().all(P::test) {
+            return Cow::Borrowed(data);
+        }
+        let mut sanitized = String::with_capacity(data.len());
+        for (i, c) in data.char_indices() {
+            if P::test(c) {
+                sanitized.push(c);
+            } else {
+                report.record(i..i + c.len_utf8(), "", "forbidden char");
+            }
+        }
+        Cow::Owned(sanitized)
+    }
+}
+
+/// Applies the repairs of `A` and `B` one after the other, truncating last (see [Sanitize::TRUNCATES]), so that
+/// e.g. `And<MaxLen<8>, CharsAll<AsciiLowercase>>` filters out the forbidden chars before cutting the rest down to
+/// length.  This only produces valid data if the second repair doesn't break the first, which is the case when it
+/// only removes chars (as the repairs of [MaxLen] and [CharsAll] do) and the first is preserved by that.  The
+/// ranges of the changes recorded by the second repair refer to the output of the first.
+impl<A: Sanitize, B: Sanitize> Sanitize for And<A, B> {
+    const TRUNCATES: bool = A::TRUNCATES || B::TRUNCATES;
+    fn sanitize<'a>(data: &'a str, report: &mut SanitizeReport) -> Cow<'a, str> {
+        if A::TRUNCATES && !B::TRUNCATES {
+            sanitize_in_turn::<B, A>(data, report)
+        } else {
+            sanitize_in_turn::<A, B>(data, report)
+        }
+    }
+}
+
+/// Applies the repair of `First` and then that of `Second`.
+fn sanitize_in_turn<'a, First: Sanitize, Second: Sanitize>(
+    data: &'a str,
+    report: &mut SanitizeReport,
+) -> Cow<'a, str> {
+    match First::sanitize(data, report) {
+        Cow::Borrowed(data) => Second::sanitize(data, report),
+        Cow::Owned(data) => Cow::Owned(Second::sanitize(&data, report).into_owned()),
+    }
+}
+
+/// Declares that every value valid for `Self` is also valid for `W`, which is what makes
+/// [ValidatedStr::weaken](crate::ValidatedStr::weaken) and [ValidatedString::weaken](crate::ValidatedString::weaken)
+/// free.  The index type `I` encodes where `W` was found within `Self` (e.g. `AndLeft<Reflexive>` for `A` within
+/// `And<A, B>`), which keeps the impls from overlapping.  It's inferred, unless there's more than one way to derive
+/// the implication (e.g. `And<A, B>` implies `Or<B, C>` both via `B` and via `Or`), in which case it can be given
+/// explicitly or the conversion done in steps.
+///
+/// # Safety
+/// Implementing this for validators where the implication doesn't actually hold breaks the validation constraint
+/// of [ValidatedStr](crate::ValidatedStr) and [ValidatedString](crate::ValidatedString).
+pub unsafe trait Implies<W, I> {}
+
+/// [Implies] index: every validator implies itself.
+pub struct Reflexive;
+/// [Implies] index: `And<A, B>` implies whatever `A` implies.
+pub struct AndLeft<I>(PhantomData<I>);
+/// [Implies] index: `And<A, B>` implies whatever `B` implies.
+pub struct AndRight<I>(PhantomData<I>);
+/// [Implies] index: whatever implies `A` implies `Or<A, B>`.
+pub struct OrLeft<I>(PhantomData<I>);
+/// [Implies] index: whatever implies `B` implies `Or<A, B>`.
+pub struct OrRight<I>(PhantomData<I>);
+
+unsafe impl<V> Implies<V, Reflexive> for V {}
+unsafe impl<A, B, W, I> Implies<W, AndLeft<I>> for And<A, B> where A: Implies<W, I> {}
+unsafe impl<A, B, W, I> Implies<W, AndRight<I>> for And<A, B> where B: Implies<W, I> {}
+unsafe impl<V, A, B, I> Implies<Or<A, B>, OrLeft<I>> for V where V: Implies<A, I> {}
+unsafe impl<V, A, B, I> Implies<Or<A, B>, OrRight<I>> for V where V: Implies<B, I> {}
+
+/// A unit in which the length of a str is measured, for use in [Bounded].
+pub trait LengthUnit {
+    /// Human-readable name of the unit (plural), for use in error messages.
+    const NAME: &'static str;
+    /// The length of the given str in this unit.
+    fn len(s: &str) -> usize;
+    /// The longest prefix of the given str that's at most `max` long in this unit.
+    fn truncate(s: &str, max: usize) -> &str;
+}
+
+/// Length measured in bytes, i.e. [str::len].
+pub struct Bytes;
+
+impl LengthUnit for Bytes {
+    const NAME: &'static str = "bytes";
+    fn len(s: &str) -> usize {
+        s.len()
+    }
+    fn truncate(s: &str, max: usize) -> &str {
+        if s.len() <= max {
+            return s;
+        }
+        let mut end = max;
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        &s[..end]
+    }
+}
+
+/// Length measured in chars (i.e. Unicode scalar values).
+pub struct Chars;
+
+impl LengthUnit for Chars {
+    const NAME: &'static str = "chars";
+    fn len(s: &str) -> usize {
+        s.chars().count()
+    }
+    fn truncate(s: &str, max: usize) -> &str {
+        match s.char_indices().nth(max) {
+            Some((end, _)) => &s[..end],
+            None => s,
+        }
+    }
+}
+
+/// Length measured in extended grapheme clusters, i.e. user-perceived characters.  Requires the
+/// `unicode-segmentation` feature.
+#[cfg(feature = "unicode-segmentation")]
+pub struct Graphemes;
+
+#[cfg(feature = "unicode-segmentation")]
+impl LengthUnit for Graphemes {
+    const NAME: &'static str = "graphemes";
+    fn len(s: &str) -> usize {
+        use unicode_segmentation::UnicodeSegmentation;
+        s.graphemes(true).count()
+    }
+    fn truncate(s: &str, max: usize) -> &str {
+        use unicode_segmentation::UnicodeSegmentation;
+        match s.grapheme_indices(true).nth(max) {
+            Some((end, _)) => &s[..end],
+            None => s,
+        }
+    }
+}
+
+/// Valid iff the length, measured in the [LengthUnit] `U`, is in the range `MIN..=MAX`.  See
+/// [BoundedStr](crate::BoundedStr) and its relatives for the ready-made pneutypes.
+pub struct Bounded<U, const MIN: usize, const MAX: usize>(PhantomData<U>);
+
+impl<U: LengthUnit, const MIN: usize, const MAX: usize> Validate for Bounded<U, MIN, MAX> {
+    type Data = str;
+    type Error = LengthError;
+    fn validate(data: &Self::Data) -> Result<(), Self::Error> {
+        let actual = U::len(data);
+        if actual < MIN {
+            Err(LengthError::TooShort {
+                min: MIN,
+                actual,
+                unit: U::NAME,
+            })
+        } else if actual > MAX {
+            Err(LengthError::TooLong {
+                max: MAX,
+                actual,
+                unit: U::NAME,
+                position: U::truncate(data, MAX).len(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Flattens nested [Constraint::AllOf]s (or [Constraint::AnyOf]s, per `all`) into their parent, so that e.g.
+/// `And<A, And<B, C>>` is described as all of A, B and C.
+fn flatten(all: bool, constraints: [Constraint; 2]) -> Vec<Constraint> {
+    constraints
+        .into_iter()
+        .flat_map(|constraint| match constraint {
+            Constraint::AllOf(inner) if all => inner,
+            Constraint::AnyOf(inner) if !all => inner,
+            constraint => vec![constraint],
+        })
+        .collect()
+}
+
+impl<A: Describe, B: Describe> Describe for And<A, B> {
+    const TYPE_NAME: &'static str = "And";
+    fn constraint() -> Constraint {
+        Constraint::AllOf(flatten(true, [A::constraint(), B::constraint()]))
+    }
+    fn generic_type_name() -> Cow<'static, str> {
+        Cow::Owned(format!(
+            "And_{}_{}",
+            A::generic_type_name(),
+            B::generic_type_name()
+        ))
+    }
+}
+
+impl<A: Describe, B: Describe> Describe for Or<A, B> {
+    const TYPE_NAME: &'static str = "Or";
+    fn constraint() -> Constraint {
+        Constraint::AnyOf(flatten(false, [A::constraint(), B::constraint()]))
+    }
+    fn generic_type_name() -> Cow<'static, str> {
+        Cow::Owned(format!(
+            "Or_{}_{}",
+            A::generic_type_name(),
+            B::generic_type_name()
+        ))
+    }
+}
+
+impl<A: Describe> Describe for Not<A> {
+    const TYPE_NAME: &'static str = "Not";
+    fn constraint() -> Constraint {
+        Constraint::Not(Box::new(A::constraint()))
+    }
+    fn generic_type_name() -> Cow<'static, str> {
+        Cow::Owned(format!("Not_{}", A::generic_type_name()))
+    }
+}
+
+impl<const N: usize> Describe for MaxLen<N> {
+    const TYPE_NAME: &'static str = "MaxLen";
+    fn constraint() -> Constraint {
+        Constraint::Length {
+            unit: Bytes::NAME,
+            min: None,
+            max: Some(N),
+        }
+    }
+    fn generic_type_name() -> Cow<'static, str> {
+        Cow::Owned(format!("MaxLen_{}", N))
+    }
+}
+
+impl<const N: usize> Describe for MinLen<N> {
+    const TYPE_NAME: &'static str = "MinLen";
+    fn constraint() -> Constraint {
+        Constraint::Length {
+            unit: Bytes::NAME,
+            min: Some(N),
+            max: None,
+        }
+    }
+    fn generic_type_name() -> Cow<'static, str> {
+        Cow::Owned(format!("MinLen_{}", N))
+    }
+}
+
+impl<P: CharPredicate> Describe for CharsAll<P> {
+    const TYPE_NAME: &'static str = "CharsAll";
+    fn constraint() -> Constraint {
+        Constraint::Chars {
+            description: P::description(),
+            regex_class: P::regex_class(),
+        }
+    }
+    fn generic_type_name() -> Cow<'static, str> {
+        Cow::Owned(format!("CharsAll_{}", P::name()))
+    }
+}
+
+impl<U: LengthUnit, const MIN: usize, const MAX: usize> Describe for Bounded<U, MIN, MAX> {
+    const TYPE_NAME: &'static str = "Bounded";
+    fn constraint() -> Constraint {
+        Constraint::Length {
+            unit: U::NAME,
+            min: Some(MIN),
+            max: Some(MAX),
+        }
+    }
+    fn generic_type_name() -> Cow<'static, str> {
+        Cow::Owned(format!("Bounded_{}_{}_{}", U::NAME, MIN, MAX))
+    }
+}